@@ -1,9 +1,16 @@
-use rust_ts_json_compiler::{get_syntax_tree, to_json};
+use rust_ts_json_compiler::{
+    diagnostics, get_syntax_tree_recovering, to_json_variants, to_json_with, JsonOptions,
+};
+use std::env;
 use std::io::{self, Read};
+use std::process;
 
 pub fn main() -> anyhow::Result<()> {
-    // get arg from cli
-    // let args = env::args().collect::<Vec<String>>();
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let options = JsonOptions {
+        omit_optional: args.iter().any(|arg| arg == "--omit-optional"),
+        null_for_nullable: args.iter().any(|arg| arg == "--null-for-nullable"),
+    };
 
     let mut buffer = String::new();
     io::stdin().read_to_string(&mut buffer)?;
@@ -14,7 +21,21 @@ pub fn main() -> anyhow::Result<()> {
 
     // let file_content = std::fs::read_to_string(&args[1])?;
 
-    let json = to_json(&get_syntax_tree(&buffer).unwrap());
+    let (zod, errors) = get_syntax_tree_recovering(&buffer);
+    if !errors.is_empty() {
+        for err in &errors {
+            eprintln!("{}", diagnostics::render(&buffer, err));
+        }
+        eprintln!(
+            "error: could not compile schema due to {} error(s)",
+            errors.len()
+        );
+        process::exit(1);
+    }
+    let json = match to_json_variants(&zod, &options) {
+        Some(variants) if args.iter().any(|arg| arg == "--variants") => variants,
+        _ => to_json_with(&zod, &options),
+    };
 
     println!("{}", json);
 
@@ -1,54 +1,767 @@
 use crate::{
-    lexer::{Lexer, Token},
-    syntax_tree::{SyntaxTree, ZodExpression},
+    lexer::{Lexer, Number, Spanned},
+    regex::{self, Pattern},
+    syntax_tree::{
+        format_date, object_fields, parse_date, property_key, Bound, DateChecks, DatetimeOptions,
+        IpVersion, NumberChecks, StringChecks, StringFormat, StringTransform, SyntaxTree,
+        UnknownKeys, Value, ZodExpression,
+    },
+    Error,
 };
 
-pub fn get_syntax_tree(schema: &str) -> Option<ZodExpression> {
-    let mut lx = Lexer::new(schema);
-    let mut tokens = Vec::new();
-
-    loop {
-        let token = lx.next_token();
-        if token == Token::Eof {
-            break;
-        }
+pub fn get_syntax_tree(schema: &str) -> Result<Spanned<ZodExpression>, Error> {
+    SyntaxTree::new(Lexer::new(schema)).parse()
+}
 
-        tokens.push(token);
-    }
+/// Parses `schema` reporting every lexer and syntax error instead of the first
+/// one. The returned tree contains `ZodExpression::Error` where parsing failed.
+pub fn get_syntax_tree_recovering(schema: &str) -> (Spanned<ZodExpression>, Vec<Error>) {
+    SyntaxTree::new(Lexer::new(schema)).parse_recovering()
+}
 
-    SyntaxTree::new(tokens.into_iter().peekable()).parse()
+/// Controls how `to_json_with` renders optional and nullable values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonOptions {
+    /// Leave out object fields marked `.optional()` or `.nullish()`.
+    pub omit_optional: bool,
+    /// Render `.nullable()` and `.nullish()` values as `null` instead of an
+    /// example of the wrapped type.
+    pub null_for_nullable: bool,
 }
 
+/// Renders an example payload with every field present and no `null`s.
 pub fn to_json(zod: &ZodExpression) -> String {
+    to_json_with(zod, &JsonOptions::default())
+}
+
+pub fn to_json_with(zod: &ZodExpression, options: &JsonOptions) -> String {
     match zod {
-        ZodExpression::Object(obj) => {
-            let mut json = String::new();
-            json.push('{');
-            json.push_str(
-                &obj.iter()
-                    .map(|(key, value)| format!("\"{}\": {}", key, to_json(value)))
-                    .collect::<Vec<String>>()
-                    .join(", "),
-            );
-
-            json.push('}');
-            json
+        ZodExpression::Object(obj, unknown_keys) => {
+            let mut fields = obj
+                .iter()
+                .filter(|field| !is_undefined(&field.value))
+                .filter(|field| !(options.omit_optional && is_optional(&field.value)))
+                .map(|field| (field.key.node.as_str(), to_json_with(&field.value, options)))
+                .collect::<Vec<_>>();
+
+            // One sample key for the catchall, named like `z.record()` keys.
+            if let UnknownKeys::Catchall(schema) = unknown_keys {
+                if !is_undefined(schema) && obj.iter().all(|field| field.key.node != "string") {
+                    fields.push(("string", to_json_with(schema, options)));
+                }
+            }
+
+            object_to_json(fields.into_iter())
         }
-        ZodExpression::Number => "1".to_string(),
-        ZodExpression::String => "\"string\"".to_string(),
-        ZodExpression::UUID => "\"aa5ac446-7e1d-11ee-b962-0242ac120002\"".to_string(),
+        ZodExpression::Number(checks) => number_example(checks),
+        // Unsatisfiable only in trees recovered from errors.
+        ZodExpression::String(checks) => match string_example(checks) {
+            Some(example) => json_string(&example),
+            None => "null".to_string(),
+        },
         ZodExpression::Boolean => "true".to_string(),
         ZodExpression::Array(array) => {
             let mut json = String::new();
             json.push('[');
-            json.push_str(&to_json(array));
+            json.push_str(&to_json_with(array, options));
             json.push(']');
             json
         }
-        ZodExpression::Literal(l) => format!("\"{}\"", l),
-        ZodExpression::Email => "\"admin@admin.com\"".to_string(),
-        ZodExpression::Any => "{}".to_string(),
-        ZodExpression::Enum(e) => format!("\"{}\"", e.first().unwrap()),
-        ZodExpression::Union(u) => to_json(u.first().unwrap()),
+        ZodExpression::Literal(value) => value_to_json(value),
+        ZodExpression::Any | ZodExpression::Unknown => "{}".to_string(),
+        ZodExpression::Null | ZodExpression::NaN => "null".to_string(),
+        // Left out of objects, like `JSON.stringify` does.
+        ZodExpression::Undefined
+        | ZodExpression::Void
+        | ZodExpression::Never
+        | ZodExpression::Symbol => "null".to_string(),
+        // `JSON.stringify` can't serialize bigints, so emit a plain integer.
+        ZodExpression::BigInt => "1".to_string(),
+        ZodExpression::Date(checks) => json_string(&format_date(date_example(checks))),
+        // Empty only in trees recovered from errors, e.g. `z.enum([1])`.
+        ZodExpression::Enum(e) => match e.first() {
+            Some(value) => json_string(value),
+            None => "null".to_string(),
+        },
+        ZodExpression::NativeEnum(native) => match native.members.first() {
+            Some((_, value)) => value_to_json(value),
+            None => "null".to_string(),
+        },
+        ZodExpression::Union(u) => match u.first() {
+            Some(option) => to_json_with(option, options),
+            None => "null".to_string(),
+        },
+        ZodExpression::Intersection(a, b) => intersection_to_json(&[a, b], options),
+        ZodExpression::DiscriminatedUnion { options: u, .. } => match u.first() {
+            Some(option) => to_json_with(option, options),
+            None => "null".to_string(),
+        },
+        ZodExpression::Tuple { items, rest } => format!(
+            "[{}]",
+            items
+                .iter()
+                .chain(rest.as_deref())
+                .map(|item| to_json_with(item, options))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        ZodExpression::Record { key, value } => {
+            let key = match key {
+                Some(key) => to_json_with(key, options),
+                None => "\"string\"".to_string(),
+            };
+            // Numeric keys like `z.number()` still become strings in JSON.
+            let key = if key.starts_with('"') {
+                key
+            } else {
+                json_string(&key)
+            };
+
+            format!("{{{}: {}}}", key, to_json_with(value, options))
+        }
+        ZodExpression::Map { key, value } => format!(
+            "[[{}, {}]]",
+            to_json_with(key, options),
+            to_json_with(value, options)
+        ),
+        // A single element can't repeat.
+        ZodExpression::Set(item) => format!("[{}]", to_json_with(item, options)),
+        ZodExpression::Optional(inner) => to_json_with(inner, options),
+        ZodExpression::Nullable(_) | ZodExpression::Nullish(_) if options.null_for_nullable => {
+            "null".to_string()
+        }
+        ZodExpression::Nullable(inner) | ZodExpression::Nullish(inner) => {
+            to_json_with(inner, options)
+        }
+        ZodExpression::Default(_, value) => value_to_json(value),
+        ZodExpression::Error => "null".to_string(),
+    }
+}
+
+fn object_to_json<'a>(fields: impl Iterator<Item = (&'a str, String)>) -> String {
+    format!(
+        "{{{}}}",
+        fields
+            .map(|(key, value)| format!("{}: {}", json_string(key), value))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// Renders a value that satisfies every schema in `schemas`. Objects are
+/// merged key by key and strings or numbers combine their checks; otherwise
+/// the most specific schema is rendered.
+fn intersection_to_json(schemas: &[&ZodExpression], options: &JsonOptions) -> String {
+    let mut fields = vec![];
+    if schemas.iter().all(|zod| object_fields(zod, &mut fields)) {
+        let mut merged: Vec<(&str, Vec<&ZodExpression>)> = vec![];
+        for field in fields {
+            match merged.iter_mut().find(|(key, _)| *key == field.key.node) {
+                Some((_, values)) => values.push(&field.value),
+                None => merged.push((&field.key.node, vec![&field.value])),
+            }
+        }
+
+        return object_to_json(
+            merged
+                .into_iter()
+                .filter(|(_, values)| !values.iter().any(|value| is_undefined(value)))
+                .filter(|(_, values)| {
+                    !(options.omit_optional && values.iter().all(|value| is_optional(value)))
+                })
+                .map(|(key, values)| (key, intersection_to_json(&values, options))),
+        );
+    }
+
+    let mut flattened = vec![];
+    for zod in schemas {
+        flatten_intersection(zod, &mut flattened);
+    }
+    let schemas = &flattened;
+    if let Some(zod) = merge_checks(schemas) {
+        return to_json_with(&zod, options);
+    }
+
+    // Literals are the most specific, `z.any()` and `z.unknown()` the least.
+    let zod = schemas
+        .iter()
+        .rev()
+        .find(|zod| matches!(zod, ZodExpression::Literal(_)))
+        .or_else(|| {
+            schemas
+                .iter()
+                .find(|zod| !matches!(zod, ZodExpression::Any | ZodExpression::Unknown))
+        })
+        .unwrap_or(&schemas[0]);
+
+    to_json_with(zod, options)
+}
+
+/// Collects the schemas of nested `.and()` calls.
+fn flatten_intersection<'a>(zod: &'a ZodExpression, out: &mut Vec<&'a ZodExpression>) {
+    match zod {
+        ZodExpression::Intersection(a, b) => {
+            flatten_intersection(a, out);
+            flatten_intersection(b, out);
+        }
+        _ => out.push(zod),
+    }
+}
+
+/// Strings or numbers with the checks of every schema in `schemas`, e.g. a
+/// `z.string().min(10)` and `.email()` giving a long email. `z.any()` and
+/// `z.unknown()` add nothing; any other type gives `None`.
+fn merge_checks(schemas: &[&ZodExpression]) -> Option<ZodExpression> {
+    let mut merged = None;
+    for zod in schemas
+        .iter()
+        .filter(|zod| !matches!(zod, ZodExpression::Any | ZodExpression::Unknown))
+    {
+        merged = Some(match (merged, zod) {
+            (None, ZodExpression::String(checks)) => ZodExpression::String(checks.clone()),
+            (None, ZodExpression::Number(checks)) => ZodExpression::Number(checks.clone()),
+            (Some(ZodExpression::String(mut checks)), ZodExpression::String(other)) => {
+                checks.merge(other);
+                ZodExpression::String(checks)
+            }
+            (Some(ZodExpression::Number(mut checks)), ZodExpression::Number(other)) => {
+                checks.merge(other);
+                ZodExpression::Number(checks)
+            }
+            _ => return None,
+        });
+    }
+
+    merged
+}
+
+/// Renders one example per option of a top-level `z.discriminatedUnion`, as
+/// a JSON object keyed by discriminator value. Returns `None` for any other
+/// schema.
+pub fn to_json_variants(zod: &ZodExpression, options: &JsonOptions) -> Option<String> {
+    let ZodExpression::DiscriminatedUnion {
+        discriminator,
+        options: variants,
+    } = zod
+    else {
+        return None;
+    };
+
+    let variants = variants
+        .iter()
+        .filter_map(|variant| {
+            let ZodExpression::Object(fields, _) = &variant.node else {
+                return None;
+            };
+            let field = fields
+                .iter()
+                .find(|field| field.key.node == *discriminator)?;
+            let ZodExpression::Literal(value) = &field.value.node else {
+                return None;
+            };
+            Some(format!(
+                "{}: {}",
+                json_string(&property_key(value)),
+                to_json_with(variant, options)
+            ))
+        })
+        .collect::<Vec<String>>();
+
+    Some(format!("{{{}}}", variants.join(", ")))
+}
+
+/// Builds a string that passes `checks`: an example of its format or regex if
+/// it has one, otherwise the required prefix, substrings and suffix around a filler
+/// that is padded or cut to fit the length bounds.
+/// Longest example string built, so a huge `.min()` can't exhaust memory.
+const MAX_STRING_LENGTH: usize = 1_000_000;
+
+/// Builds a string meeting every check, or returns `None` when that fails,
+/// e.g. for `.regex(/^\d{2}$/).min(3)`.
+pub(crate) fn string_example(checks: &StringChecks) -> Option<String> {
+    if let Some(format) = checks.format {
+        return format_example_with(format, checks);
+    }
+    let min = checks.min.unwrap_or(0).min(MAX_STRING_LENGTH);
+    let max = checks.max.unwrap_or(usize::MAX);
+
+    let Some(regex) = &checks.regex else {
+        let prefix = checks.starts_with.as_deref().unwrap_or("");
+        return Some(padded_example(checks, prefix, min, max))
+            .filter(|example| satisfies(example, checks));
+    };
+    if min > regex::MAX_EXAMPLE_LENGTH {
+        return None;
+    }
+    let pattern = regex::parse(&regex.pattern).ok()?;
+    let example = pattern.example_with_length(min, max);
+    let fitted = example
+        .as_deref()
+        .and_then(|example| fit_checks(&pattern, example, checks));
+    // Unless the pattern is anchored at the end, filler can follow a match.
+    let padded = pattern
+        .example()
+        .map(|example| padded_example(checks, &example, min, max));
+
+    [example, fitted, padded]
+        .into_iter()
+        .flatten()
+        .find(|example| satisfies(example, checks) && pattern.is_match(example))
+}
+
+/// Fits the affixes and missing substrings into an example of `pattern`,
+/// e.g. `10.168.0.1` for `.ip().startsWith("10.")` or `ba/0` for
+/// `.regex(/^[a-z]+\/\d$/).includes("b")`.
+fn fit_checks(pattern: &Pattern, example: &str, checks: &StringChecks) -> Option<String> {
+    let prefix = checks.starts_with.as_deref().unwrap_or("");
+    let suffix = checks.ends_with.as_deref().unwrap_or("");
+
+    let mut example = example.to_string();
+    if !example.starts_with(prefix) {
+        let first = prefix.len().min(example.len());
+        example = splice_points(&example, first, 0..=example.len())
+            .into_iter()
+            .map(|cut| format!("{prefix}{}", &example[cut..]))
+            .find(|candidate| pattern.is_match(candidate))?;
+    }
+    if !example.ends_with(suffix) {
+        let first = example.len().saturating_sub(suffix.len()).max(prefix.len());
+        example = splice_points(&example, first, (prefix.len()..=example.len()).rev())
+            .into_iter()
+            .map(|cut| format!("{}{suffix}", &example[..cut]))
+            .find(|candidate| pattern.is_match(candidate))?;
+    }
+    for part in &checks.includes {
+        if example.contains(part.as_str()) {
+            continue;
+        }
+        // Between the affixes, so they stay in place.
+        let (start, end) = (prefix.len(), example.len() - suffix.len());
+        example = splice_points(&example, start, start..=end.max(start))
+            .into_iter()
+            .map(|index| format!("{}{part}{}", &example[..index], &example[index..]))
+            .find(|candidate| pattern.is_match(candidate))?;
+    }
+
+    Some(example)
+}
+
+/// Byte offsets to splice `text` at: `first`, then every other offset in
+/// `rest` if `text` is short enough to search.
+fn splice_points(text: &str, first: usize, rest: impl Iterator<Item = usize>) -> Vec<usize> {
+    let short = text.len() <= 100;
+
+    std::iter::once(first)
+        .chain(rest.filter(|_| short))
+        .filter(|index| text.is_char_boundary(*index))
+        .collect()
+}
+
+/// `head` followed by the missing substrings, the suffix and filler up to
+/// `min` characters.
+fn padded_example(checks: &StringChecks, head: &str, min: usize, max: usize) -> String {
+    let suffix = checks.ends_with.as_deref().unwrap_or("");
+    let middle = missing_includes(checks, &[head, suffix]);
+
+    let fixed = js_length(head) + js_length(&middle) + js_length(suffix);
+
+    let mut filler = if fixed == 0 {
+        "string".to_string()
+    } else {
+        String::new()
+    };
+    let mut padding = "string".chars().cycle();
+    while fixed + filler.len() < min {
+        filler.extend(padding.next());
+    }
+    filler.truncate(max.saturating_sub(fixed).min(filler.len()));
+
+    for transform in &checks.transforms {
+        match transform {
+            StringTransform::Trim => {}
+            StringTransform::ToLowerCase => filler = filler.to_lowercase(),
+            StringTransform::ToUpperCase => filler = filler.to_uppercase(),
+        }
+    }
+
+    format!("{head}{middle}{filler}{suffix}")
+}
+
+/// The `.includes()` substrings not already found in any of `parts`.
+fn missing_includes(checks: &StringChecks, parts: &[&str]) -> String {
+    checks
+        .includes
+        .iter()
+        .filter(|part| !parts.iter().any(|other| other.contains(part.as_str())))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Builds an example of `format` around the other string checks where the
+/// format allows it, e.g. `admin@corp.com` for `.email().endsWith("@corp.com")`.
+/// Emails and URLs are built from a short or a regular host, padded to the
+/// minimum length; other formats resize and overwrite their fixed example as
+/// far as the format's pattern allows.
+fn format_example_with(format: StringFormat, checks: &StringChecks) -> Option<String> {
+    let min = checks.min.unwrap_or(0).min(MAX_STRING_LENGTH);
+    let build: fn(&StringChecks, bool, usize) -> Option<String> = match format {
+        StringFormat::Email => email_example,
+        StringFormat::Url => url_example,
+        _ => {
+            let example = format_example(format);
+            let Some(pattern) = format_pattern(format).and_then(|p| regex::parse(p).ok()) else {
+                return Some(example).filter(|example| satisfies(example, checks));
+            };
+            let max = checks.max.unwrap_or(usize::MAX);
+            let example = resize(&pattern, example, min, max);
+            let fitted = fit_checks(&pattern, &example, checks);
+
+            return [Some(example), fitted]
+                .into_iter()
+                .flatten()
+                .find(|example| satisfies(example, checks) && pattern.is_match(example));
+        }
+    };
+
+    [false, true].into_iter().find_map(|short| {
+        let padding = min.saturating_sub(js_length(&build(checks, short, 0)?));
+        build(checks, short, padding).filter(|example| satisfies(example, checks))
+    })
+}
+
+/// Pads `example` to `min` characters or cuts it down to `max`, at the first
+/// length `pattern` accepts, e.g. a multiple of 4 for base64.
+fn resize(pattern: &Pattern, example: String, min: usize, max: usize) -> String {
+    let len = example.chars().count();
+    let lengths: Vec<usize> = if len < min {
+        (min..min + 4)
+            .take_while(|len| *len <= max.min(regex::MAX_EXAMPLE_LENGTH))
+            .collect()
+    } else if len > max {
+        (0..=max).rev().collect()
+    } else {
+        return example;
+    };
+
+    lengths
+        .into_iter()
+        .map(|len| {
+            let mut resized: String = example.chars().take(len).collect();
+            resized.extend(std::iter::repeat_n('a', len.saturating_sub(resized.len())));
+            resized
+        })
+        .find(|resized| pattern.is_match(resized))
+        .unwrap_or(example)
+}
+
+/// An email with the required prefix as its local part and the suffix ending
+/// its domain, or spanning both when it contains `@`.
+fn email_example(checks: &StringChecks, short: bool, padding: usize) -> Option<String> {
+    let prefix = checks.starts_with.as_deref().unwrap_or("");
+    let suffix = checks.ends_with.as_deref().unwrap_or("");
+    let name = if short { "a" } else { "admin" };
+    let default = if short { "a.co" } else { "admin.com" };
+
+    let head = match prefix.split_once('@') {
+        Some((head, "")) => head,
+        Some(_) => return None,
+        None => prefix,
+    };
+    let (tail, domain) = match suffix.split_once('@') {
+        Some((tail, domain)) => (tail, domain.to_string()),
+        None if default.ends_with(suffix) => ("", default.to_string()),
+        None if suffix.starts_with('.') => ("", format!("{name}{suffix}")),
+        None if suffix.contains('.') => ("", suffix.to_string()),
+        None => ("", format!("{name}.{suffix}")),
+    };
+
+    let middle = missing_includes(checks, &[head, tail, &domain]);
+    let mut local = format!("{head}{middle}");
+    if local.is_empty() && tail.is_empty() {
+        local.push_str(name);
+    }
+    local.extend(std::iter::repeat_n('a', padding));
+
+    Some(format!("{local}{tail}@{domain}"))
+}
+
+/// A URL starting with the required prefix, with substrings and padding in
+/// its path and the suffix appended as a path unless it already ends it.
+fn url_example(checks: &StringChecks, short: bool, padding: usize) -> Option<String> {
+    let prefix = checks.starts_with.as_deref().unwrap_or("");
+    let suffix = checks.ends_with.as_deref().unwrap_or("");
+    let host = if short { "a.co" } else { "example.com" };
+    let base = format!("https://{host}");
+
+    let mut url = match prefix.split_once("://") {
+        _ if base.starts_with(prefix) => base,
+        Some((_, rest)) if rest.is_empty() || rest.ends_with('.') => format!("{prefix}{host}"),
+        Some(_) => prefix.to_string(),
+        None if prefix.chars().all(|ch| ch.is_ascii_alphabetic()) => {
+            format!("{prefix}://{host}")
+        }
+        None => return None,
+    };
+
+    let middle = missing_includes(checks, &[&url, suffix]);
+    let has_path = !middle.is_empty() || padding > 0;
+    if has_path {
+        let padding = if middle.is_empty() {
+            padding - 1
+        } else {
+            padding
+        };
+        url.push('/');
+        url.push_str(&middle);
+        url.extend(std::iter::repeat_n('a', padding));
+    }
+    if !url.ends_with(suffix) {
+        if !has_path && !suffix.starts_with(['/', '?', '#']) {
+            url.push('/');
+        }
+        url.push_str(suffix);
+    }
+
+    Some(url)
+}
+
+/// Whether `example` meets the length, affix and substring checks.
+fn satisfies(example: &str, checks: &StringChecks) -> bool {
+    let len = js_length(example);
+
+    checks.min.is_none_or(|min| len >= min)
+        && checks.max.is_none_or(|max| len <= max)
+        && checks
+            .starts_with
+            .as_ref()
+            .is_none_or(|prefix| example.starts_with(prefix.as_str()))
+        && checks
+            .ends_with
+            .as_ref()
+            .is_none_or(|suffix| example.ends_with(suffix.as_str()))
+        && checks
+            .includes
+            .iter()
+            .all(|part| example.contains(part.as_str()))
+}
+
+/// The pattern zod checks `format` with, where it's simple enough to
+/// resize and overwrite examples by.
+fn format_pattern(format: StringFormat) -> Option<&'static str> {
+    let pattern = match format {
+        StringFormat::Uuid => {
+            r"^[\da-fA-F]{8}-[\da-fA-F]{4}-[\da-fA-F]{4}-[\da-fA-F]{4}-[\da-fA-F]{12}$"
+        }
+        StringFormat::Nanoid => r"^[\w-]{21}$",
+        StringFormat::Cuid => r"^c[^\s-]{8,}$",
+        StringFormat::Cuid2 => r"^[\da-z]+$",
+        StringFormat::Ulid => r"^[\dA-HJKMNP-TV-Z]{26}$",
+        StringFormat::Date => r"^\d{4}-(?:0[1-9]|1[0-2])-(?:0[1-9]|[12]\d|3[01])$",
+        StringFormat::Time(_) => r"^(?:[01]\d|2[0-3]):[0-5]\d:[0-5]\d(?:\.\d+)?$",
+        StringFormat::Datetime(_) => {
+            r"^\d{4}-(?:0[1-9]|1[0-2])-(?:0[1-9]|[12]\d|3[01])T(?:[01]\d|2[0-3]):[0-5]\d:[0-5]\d(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})$"
+        }
+        StringFormat::Ip(None | Some(IpVersion::V4)) => {
+            r"^(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)$"
+        }
+        StringFormat::Cidr(None | Some(IpVersion::V4)) => {
+            r"^(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)/(?:3[0-2]|[12]?\d)$"
+        }
+        StringFormat::Base64 => r"^(?:[\da-zA-Z+/]{4})*(?:[\da-zA-Z+/]{2}==|[\da-zA-Z+/]{3}=)?$",
+        StringFormat::Base64Url => r"^(?:[\w-]{4})*(?:[\w-]{2}(?:==)?|[\w-]{3}=?)?$",
+        _ => return None,
+    };
+
+    Some(pattern)
+}
+
+fn format_example(format: StringFormat) -> String {
+    let example = match format {
+        StringFormat::Email => "admin@admin.com",
+        StringFormat::Url => "https://example.com",
+        StringFormat::Emoji => "😀",
+        StringFormat::Uuid => "aa5ac446-7e1d-11ee-b962-0242ac120002",
+        StringFormat::Nanoid => "V1StGXR8_Z5jdHi6B-myT",
+        StringFormat::Cuid => "cjld2cjxh0000qzrmn831i7rn",
+        StringFormat::Cuid2 => "tz4a98xxat96iws9zmbrgj3a",
+        StringFormat::Ulid => "01ARZ3NDEKTSV4RRFFQ69G5FAV",
+        StringFormat::Datetime(DatetimeOptions { precision, .. }) => {
+            return format!("2023-11-08T12:00:00{}Z", fraction(precision));
+        }
+        StringFormat::Date => "2023-11-08",
+        StringFormat::Time(precision) => return format!("12:00:00{}", fraction(precision)),
+        StringFormat::Duration => "P3Y6M4DT12H30M5S",
+        StringFormat::Ip(Some(IpVersion::V6)) => "2001:db8::1",
+        StringFormat::Ip(_) => "192.168.0.1",
+        StringFormat::Cidr(Some(IpVersion::V6)) => "2001:db8::/32",
+        StringFormat::Cidr(_) => "192.168.0.0/24",
+        StringFormat::Base64 | StringFormat::Base64Url => "c3RyaW5n",
+    };
+
+    example.to_string()
+}
+
+/// Fractional seconds with exactly `precision` digits, if it is fixed.
+fn fraction(precision: Option<u32>) -> String {
+    match precision {
+        Some(0) | None => String::new(),
+        Some(precision) => format!(".{}", "0".repeat(precision as usize)),
+    }
+}
+
+/// Length of `value` as JavaScript counts it, in UTF-16 code units.
+fn js_length(value: &str) -> usize {
+    value.encode_utf16().count()
+}
+
+/// Picks a date within the bounds, preferring 2023-11-08T12:00:00Z.
+fn date_example(checks: &DateChecks) -> i64 {
+    let date = parse_date("2023-11-08T12:00:00Z").unwrap();
+    let date = checks.min.map_or(date, |min| date.max(min));
+
+    checks.max.map_or(date, |max| date.min(max))
+}
+
+/// Picks a number that passes `checks`, preferring `1` when it does.
+fn number_example(checks: &NumberChecks) -> String {
+    const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+    let mut checks = checks.clone();
+    if checks.safe {
+        checks.set_min(Bound::inclusive(-MAX_SAFE_INTEGER));
+        checks.set_max(Bound::inclusive(MAX_SAFE_INTEGER));
+    }
+
+    let min = checks.min.map(|bound| bound.value);
+    let max = checks.max.map(|bound| bound.value);
+    let target = 1f64
+        .max(min.unwrap_or(f64::MIN))
+        .min(max.unwrap_or(f64::MAX));
+
+    let step = match checks.multiple_of.map(f64::abs).filter(|step| *step > 0.0) {
+        Some(step) if checks.int => Some(integer_multiple(step)),
+        Some(step) => Some(step),
+        None if checks.int => Some(1.0),
+        None => None,
+    };
+
+    let value = match step {
+        Some(step) => {
+            let up = round_to_step((target / step).ceil() * step, step);
+            let down = round_to_step((target / step).floor() * step, step);
+
+            [
+                up,
+                round_to_step(up + step, step),
+                down,
+                round_to_step(down - step, step),
+            ]
+            .into_iter()
+            .find(|value| checks.accepts(*value))
+            .unwrap_or(up)
+        }
+        None if checks.accepts(target) => target,
+        // `target` sits on an exclusive bound.
+        None => match (min, max) {
+            (Some(min), Some(max)) => (min + max) / 2.0,
+            (Some(min), None) => min + 1.0,
+            (None, Some(max)) => max - 1.0,
+            (None, None) => target,
+        },
+    };
+
+    // Avoid printing `-0`.
+    (value + 0.0).to_string()
+}
+
+/// Smallest multiple of `step` that is a whole number.
+fn integer_multiple(step: f64) -> f64 {
+    (1..=1000)
+        .map(|k| round_to_step(k as f64 * step, step))
+        .find(|value| value.fract() == 0.0)
+        .unwrap_or(step.ceil())
+}
+
+/// Rounds away floating point noise, keeping as many decimals as `step` has.
+fn round_to_step(value: f64, step: f64) -> f64 {
+    let decimals = step
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len());
+    let scale = 10f64.powi(decimals as i32);
+
+    (value * scale).round() / scale
+}
+
+/// Renders a literal schema value as JSON. Bigints become plain integers and
+/// non-finite numbers, which JSON can't represent, become `null`.
+fn value_to_json(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::Number(Number::BigInt(value)) => value.to_string(),
+        Value::Number(Number::Float(value)) if !value.is_finite() => "null".to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => json_string(value),
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(value_to_json)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Value::Object(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(key, value)| format!("{}: {}", json_string(key), value_to_json(value)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Whether a value of this schema has no JSON representation, so that it is
+/// left out of objects.
+fn is_undefined(zod: &ZodExpression) -> bool {
+    matches!(
+        zod,
+        ZodExpression::Undefined
+            | ZodExpression::Void
+            | ZodExpression::Never
+            | ZodExpression::Symbol
+    )
+}
+
+/// Whether an object field with this schema may be left out.
+fn is_optional(zod: &ZodExpression) -> bool {
+    match zod {
+        ZodExpression::Optional(_) | ZodExpression::Nullish(_) => true,
+        ZodExpression::Nullable(inner) => is_optional(inner),
+        _ => false,
+    }
+}
+
+/// Quotes `value` as a JSON string, escaping it as required by RFC 8259.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+
+    for ch in value.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            '\u{8}' => json.push_str("\\b"),
+            '\u{c}' => json.push_str("\\f"),
+            ch if ch.is_control() => json.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => json.push(ch),
+        }
     }
+
+    json.push('"');
+    json
 }
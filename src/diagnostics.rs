@@ -0,0 +1,286 @@
+use std::fmt::Display;
+
+use crate::{
+    lexer::{LexerError, Span, Token},
+    syntax_tree::{SyntaxError, ZOD_TYPES},
+    Error,
+};
+
+/// A schema error prepared for display, in the style of rustc:
+///
+/// ```text
+/// error: unknown zod type `strin`
+///  --> 1:17
+///   |
+/// 1 | z.object({ a: z.strin() })
+///   |                 ^^^^^ not a zod type
+///   |
+///   = help: did you mean `z.string()`?
+/// ```
+#[derive(Debug)]
+pub struct Diagnostic<'a> {
+    source: &'a str,
+    pub message: String,
+    pub span: Span,
+    pub label: String,
+    pub help: Option<String>,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(source: &'a str, error: &Error) -> Self {
+        let (message, label, help) = match error {
+            Error::Lexer(err) => describe_lexer_error(err),
+            Error::Syntax(err) => describe_syntax_error(err),
+        };
+
+        Self {
+            source,
+            message,
+            span: error.span(),
+            label,
+            help,
+        }
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = self.source.lines().nth(self.span.line - 1).unwrap_or("");
+        let line_number = self.span.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let before: String = line
+            .chars()
+            .take(self.span.column.saturating_sub(1))
+            .collect();
+        let offset = display_width(&before);
+        let width = self.source[self.span.start..self.span.end]
+            .lines()
+            .next()
+            .map_or(0, display_width)
+            .max(1);
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{gutter}--> {}", self.span)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line_number} | {}", line.replace('\t', TAB))?;
+        writeln!(
+            f,
+            "{gutter} | {}{} {}",
+            " ".repeat(offset),
+            "^".repeat(width),
+            self.label
+        )?;
+
+        if let Some(help) = &self.help {
+            writeln!(f, "{gutter} |")?;
+            writeln!(f, "{gutter} = help: {help}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Tabs are printed as this many spaces, as rustc does, so carets line up.
+const TAB: &str = "    ";
+
+/// Columns `text` takes up once tabs are expanded.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|ch| if ch == '\t' { TAB.len() } else { 1 })
+        .sum()
+}
+
+/// Renders `error` against the schema it was produced from.
+pub fn render(source: &str, error: &Error) -> String {
+    Diagnostic::new(source, error).to_string()
+}
+
+fn describe_lexer_error(err: &LexerError) -> (String, String, Option<String>) {
+    match err {
+        LexerError::IllegalCharacter(ch, _) => (
+            format!("illegal character `{ch}`"),
+            "not valid in a schema".to_string(),
+            None,
+        ),
+        LexerError::UnterminatedComment(_) => (
+            "unterminated block comment".to_string(),
+            "comment starts here".to_string(),
+            Some("close the comment with `*/`".to_string()),
+        ),
+        LexerError::UnterminatedString(_) => (
+            "unterminated string literal".to_string(),
+            "string starts here".to_string(),
+            Some("close the string with a matching quote".to_string()),
+        ),
+        LexerError::InvalidEscape(_) => (
+            "invalid escape sequence".to_string(),
+            "not a valid escape".to_string(),
+            Some("use `\\xHH`, `\\uHHHH` or `\\u{H...}` for character codes".to_string()),
+        ),
+        LexerError::TemplateInterpolation(_) => (
+            "template literal interpolation is not supported".to_string(),
+            "interpolation starts here".to_string(),
+            Some("use a plain string instead".to_string()),
+        ),
+        LexerError::InvalidNumber(_) => (
+            "invalid numeric literal".to_string(),
+            "not a valid number".to_string(),
+            None,
+        ),
+        LexerError::UnterminatedRegex(_) => (
+            "unterminated regular expression".to_string(),
+            "regex starts here".to_string(),
+            Some("close the pattern with `/`".to_string()),
+        ),
+        LexerError::InvalidRegexFlags(_) => (
+            "invalid regular expression flags".to_string(),
+            "unknown or repeated flag".to_string(),
+            Some("flags are any of `d`, `g`, `i`, `m`, `s`, `u`, `v` and `y`".to_string()),
+        ),
+    }
+}
+
+fn describe_syntax_error(err: &SyntaxError) -> (String, String, Option<String>) {
+    match err {
+        SyntaxError::UnexpectedToken(expected, found, _) => (
+            format!("expected {}, found {}", describe(expected), describe(found)),
+            format!("expected {}", describe(expected)),
+            match expected {
+                Token::Eof => Some("a schema must be a single zod expression".to_string()),
+                _ => None,
+            },
+        ),
+        SyntaxError::InvalidIdentifier(ident, _) => (
+            format!("unknown zod type `{ident}`"),
+            "not a zod type".to_string(),
+            suggest(ident).map(|name| match name {
+                "coerce" => "did you mean `z.coerce`?".to_string(),
+                _ => format!("did you mean `z.{name}()`?"),
+            }),
+        ),
+        SyntaxError::UnexpectedEndOfFile(_) => (
+            "unexpected end of input".to_string(),
+            "schema ends here".to_string(),
+            Some("check for unclosed brackets".to_string()),
+        ),
+        SyntaxError::UnexpectedTokenInEnum(token, _) => (
+            format!("unexpected {} in enum", describe(token)),
+            "expected a string literal".to_string(),
+            Some("enum values must be strings, e.g. `z.enum([\"a\", \"b\"])`".to_string()),
+        ),
+        SyntaxError::UnexpectedTokenInObjectBody(token, _) => (
+            format!("unexpected {} in object body", describe(token)),
+            "expected a key".to_string(),
+            Some("object fields are written as `key: z.string()`".to_string()),
+        ),
+        SyntaxError::InvalidRegex(err, _) => (
+            "invalid regular expression".to_string(),
+            err.to_string().to_lowercase(),
+            None,
+        ),
+        SyntaxError::InvalidUnionOption(discriminator, _) => (
+            format!("discriminated union option has no literal `{discriminator}` field"),
+            "not usable as an option".to_string(),
+            Some(format!(
+                "options are objects like `z.object({{ {discriminator}: z.literal(\"a\") }})`"
+            )),
+        ),
+        SyntaxError::DuplicateDiscriminator(value, _) => (
+            format!("duplicate discriminator value `{value}`"),
+            "used by an earlier option".to_string(),
+            None,
+        ),
+        SyntaxError::IntersectionConflict(key, _) => (
+            format!("key `{key}` has conflicting types in intersection"),
+            "no value satisfies both types".to_string(),
+            None,
+        ),
+        SyntaxError::InvalidObjectMethodArgument(method, _) => (
+            format!("invalid argument to `.{method}()`"),
+            "not supported here".to_string(),
+            Some(if method == "merge" {
+                "pass an object schema like `z.object({ id: z.number() })`".to_string()
+            } else {
+                "pass a mask like `{ id: true }`".to_string()
+            }),
+        ),
+        SyntaxError::UndeclaredConstant(name, _) => (
+            format!("cannot find constant `{name}`"),
+            "not declared".to_string(),
+            Some(format!(
+                "declare it before the schema, e.g. `const {name} = \"key\"`"
+            )),
+        ),
+        SyntaxError::UndeclaredEnum(name, _) => (
+            format!("cannot find enum `{name}`"),
+            "not declared".to_string(),
+            Some(format!(
+                "declare it before the schema, e.g. `enum {name} {{ A = \"A\" }}`"
+            )),
+        ),
+        SyntaxError::MissingEnumInitializer(member, _) => (
+            format!("enum member `{member}` must have an initializer"),
+            "follows a string member".to_string(),
+            Some(format!("give it a value, e.g. `{member} = \"{member}\"`")),
+        ),
+        SyntaxError::UnsatisfiableString(method, _) => (
+            format!("no example string satisfies `.{method}()` and the checks before it"),
+            "conflicts with the earlier checks".to_string(),
+            None,
+        ),
+        SyntaxError::InvalidDate(value, _) => (
+            format!("invalid date {value:?}"),
+            "not a date".to_string(),
+            Some("dates are written as `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ`".to_string()),
+        ),
+        SyntaxError::UnexpectedTokenInValue(token, _) => (
+            format!("unexpected {} in value", describe(token)),
+            "expected a literal".to_string(),
+            Some(
+                "values are strings, numbers, booleans, `null`, arrays or objects of those"
+                    .to_string(),
+            ),
+        ),
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Eof => "end of input".to_string(),
+        Token::Illegal => "illegal character".to_string(),
+        Token::Ident(ident) if ident.is_empty() => "identifier".to_string(),
+        Token::Str(_) => "string literal".to_string(),
+        Token::Regex(..) => "regular expression".to_string(),
+        Token::Number(value) => format!("number `{value}`"),
+        token => format!("`{token}`"),
+    }
+}
+
+/// Closest known zod type to `ident`, if any is near enough to be a typo.
+fn suggest(ident: &str) -> Option<&'static str> {
+    ZOD_TYPES
+        .iter()
+        .map(|name| (levenshtein(ident, name), *name))
+        .filter(|(distance, name)| *distance <= name.len() / 3 + 1)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+
+    row[b.len()]
+}
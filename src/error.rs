@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+use crate::lexer::{LexerError, Span};
+use crate::syntax_tree::SyntaxError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Lexer(#[from] LexerError),
+
+    #[error(transparent)]
+    Syntax(#[from] SyntaxError),
+}
+
+impl Error {
+    pub fn span(&self) -> Span {
+        match self {
+            Error::Lexer(err) => err.span(),
+            Error::Syntax(err) => err.span(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+use super::Span;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum LexerError {
+    #[error("Illegal character {0:?}")]
+    IllegalCharacter(char, Span),
+
+    #[error("Unterminated block comment")]
+    UnterminatedComment(Span),
+
+    #[error("Unterminated string literal")]
+    UnterminatedString(Span),
+
+    #[error("Invalid escape sequence")]
+    InvalidEscape(Span),
+
+    #[error("Template literal interpolation is not supported")]
+    TemplateInterpolation(Span),
+
+    #[error("Invalid numeric literal")]
+    InvalidNumber(Span),
+
+    #[error("Unterminated regular expression literal")]
+    UnterminatedRegex(Span),
+
+    #[error("Invalid regular expression flags")]
+    InvalidRegexFlags(Span),
+}
+
+impl LexerError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexerError::IllegalCharacter(_, span)
+            | LexerError::UnterminatedComment(span)
+            | LexerError::UnterminatedString(span)
+            | LexerError::InvalidEscape(span)
+            | LexerError::TemplateInterpolation(span)
+            | LexerError::InvalidNumber(span)
+            | LexerError::UnterminatedRegex(span)
+            | LexerError::InvalidRegexFlags(span) => *span,
+        }
+    }
+}
@@ -1,135 +1,525 @@
+use std::borrow::Cow;
 use std::iter::Peekable;
-use std::str::Chars;
+use std::str::CharIndices;
 
-use super::Token;
+use super::{LexerError, Span, Spanned, Token};
 
-pub struct Lexer<'a> {
-    input: Peekable<Chars<'a>>,
+pub type LexResult<'src> = Result<Spanned<Token<'src>>, LexerError>;
 
-    ch: u8,
+/// Splits a schema into tokens borrowing from the input.
+///
+/// The lexer is an `Iterator` over `LexResult`s that ends after yielding
+/// `Token::Eof`, so it can be handed straight to `SyntaxTree::new`.
+pub struct Lexer<'src> {
+    source: &'src str,
+    input: Peekable<CharIndices<'src>>,
+
+    ch: char,
+    offset: usize,
+    line: usize,
+    column: usize,
+
+    /// Offset, line and column where the current token starts.
+    start: (usize, usize, usize),
+    /// Set once `Token::Eof` has been yielded by the iterator.
+    done: bool,
+    /// Whether the last token ends an operand, after which `/` divides
+    /// instead of starting a regex, e.g. in `.refine((v) => v / 2 > 1)`.
+    after_operand: bool,
 }
 
 #[allow(dead_code)]
-impl<'a> Lexer<'a> {
-    pub fn new(input: &'a str) -> Self {
+impl<'src> Lexer<'src> {
+    pub fn new(input: &'src str) -> Self {
         let mut lexer = Self {
-            input: input.chars().peekable(),
-            ch: 0,
+            source: input,
+            input: input.char_indices().peekable(),
+            ch: '\0',
+            offset: 0,
+            line: 1,
+            column: 0,
+            start: (0, 1, 1),
+            done: false,
+            after_operand: false,
         };
         lexer.next_char();
         lexer
     }
 
     fn next_char(&mut self) {
-        self.ch = match self.input.peek() {
-            Some(ch) => *ch as u8,
-            None => 0,
-        };
+        if self.ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
 
-        self.input.next();
+        (self.offset, self.ch) = match self.input.next() {
+            Some((offset, ch)) => (offset, ch),
+            None => (self.source.len(), '\0'),
+        };
     }
 
-    pub fn peek(&mut self) -> u8 {
+    pub fn peek(&mut self) -> char {
         match self.input.peek() {
-            Some(ch) => *ch as u8,
-            None => 0,
+            Some((_, ch)) => *ch,
+            None => '\0',
+        }
+    }
+
+    pub fn next_token(&mut self) -> Token<'src> {
+        self.next_spanned_token().node
+    }
+
+    /// Reads the next token together with its location in the input.
+    /// Malformed input is returned as `Token::Illegal`.
+    pub fn next_spanned_token(&mut self) -> Spanned<Token<'src>> {
+        match self.lex_token() {
+            Ok(token) => token,
+            Err(err) => Spanned::new(Token::Illegal, err.span()),
         }
     }
 
-    pub fn next_token(&mut self) -> Token {
-        self.skip_whitespace();
+    fn lex_token(&mut self) -> LexResult<'src> {
+        self.skip_trivia()?;
+        self.mark_start();
+
+        let token = self.read_token()?;
+        self.after_operand = matches!(
+            token,
+            Token::Ident(_)
+                | Token::Number(_)
+                | Token::Str(_)
+                | Token::Regex(_)
+                | Token::True
+                | Token::False
+                | Token::RRound
+                | Token::RSquare
+        );
+
+        Ok(Spanned::new(token, self.span()))
+    }
+
+    /// Reads every token up to and including `Token::Eof`.
+    pub fn tokenize(self) -> Result<Vec<Spanned<Token<'src>>>, LexerError> {
+        self.collect()
+    }
+
+    fn mark_start(&mut self) {
+        self.start = (self.offset, self.line, self.column);
+    }
+
+    /// Span from the start of the current token up to the current character.
+    fn span(&self) -> Span {
+        let (start, line, column) = self.start;
 
-        match self.ch {
-            b'a'..=b'z' | b'A'..=b'Z' | b'_' => self.consume_ident(),
-            b'.' => {
+        Span::new(start, self.offset, line, column)
+    }
+
+    fn read_token(&mut self) -> Result<Token<'src>, LexerError> {
+        let token = match self.ch {
+            ch if ch.is_alphabetic() || ch == '_' || ch == '$' => self.consume_ident(),
+            '0'..='9' => self.consume_number()?,
+            '-' | '.' if self.starts_number() => self.consume_number()?,
+            '.' => {
                 self.next_char();
                 Token::Dot
             }
-            b':' => {
+            ':' => {
                 self.next_char();
                 Token::Colon
             }
-            b'0'..=b'9' => self.consume_int(),
-            b',' => {
+            '=' => {
+                self.next_char();
+                Token::Equals
+            }
+            ';' => {
+                self.next_char();
+                Token::Semicolon
+            }
+            ',' => {
                 self.next_char();
                 Token::Comma
             }
-            b'(' => {
+            '(' => {
                 self.next_char();
                 Token::LRound
             }
-            b')' => {
+            ')' => {
                 self.next_char();
                 Token::RRound
             }
-            b'{' => {
+            '{' => {
                 self.next_char();
                 Token::LCurly
             }
-            b'}' => {
+            '}' => {
                 self.next_char();
                 Token::RCurly
             }
-            b'[' => {
+            '[' => {
                 self.next_char();
                 Token::LSquare
             }
-            b']' => {
+            ']' => {
                 self.next_char();
                 Token::RSquare
             }
-            b'"' | b'\'' => self.consume_string(),
-            0 => Token::Eof,
-            _ => {
+            '"' | '\'' | '`' => self.consume_string()?,
+            '/' if self.rest().starts_with("/**") => self.consume_doc_comment()?,
+            '/' if !self.after_operand => self.consume_regex()?,
+            '\0' => Token::Eof,
+            ch if ch.is_ascii_punctuation() => {
                 self.next_char();
-                Token::Illegal
+                Token::Punct(ch)
             }
+            ch => {
+                self.next_char();
+                return Err(LexerError::IllegalCharacter(ch, self.span()));
+            }
+        };
+
+        Ok(token)
+    }
+
+    /// Input from the current character onwards.
+    fn rest(&self) -> &'src str {
+        &self.source[self.offset..]
+    }
+
+    /// Reads a `/** ... */` block, stripping the delimiters and the leading
+    /// `*` of every line.
+    fn consume_doc_comment(&mut self) -> Result<Token<'src>, LexerError> {
+        let body = self.consume_block_comment()?;
+        let body = body.strip_prefix('*').unwrap_or(body);
+
+        let mut lines: Vec<&'src str> = body
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                line.strip_prefix('*').unwrap_or(line).trim()
+            })
+            .skip_while(|line| line.is_empty())
+            .collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
         }
+
+        Ok(Token::DocComment(match lines.as_slice() {
+            [] => Cow::Borrowed(""),
+            [line] => Cow::Borrowed(line),
+            lines => Cow::Owned(lines.join("\n")),
+        }))
     }
 
-    fn consume_string(&mut self) -> Token {
-        let mut value = String::new();
-        let quote_type = self.ch;
-        let mut next_skip = false;
+    /// Consumes a `/* ... */` comment and returns the text between the delimiters.
+    fn consume_block_comment(&mut self) -> Result<&'src str, LexerError> {
+        let body = &self.rest()[2..];
+        let len = match body.find("*/") {
+            Some(len) => len,
+            None => {
+                while self.ch != '\0' {
+                    self.next_char();
+                }
+                return Err(LexerError::UnterminatedComment(self.span()));
+            }
+        };
+
+        let end = self.offset + 2 + len + 2;
+        while self.offset < end {
+            self.next_char();
+        }
+
+        Ok(&body[..len])
+    }
 
+    /// Reads a `/pattern/flags` regular expression, which is how a `/` that
+    /// doesn't start a comment or follow an operand is read.
+    fn consume_regex(&mut self) -> Result<Token<'src>, LexerError> {
         self.next_char();
 
-        while self.ch != quote_type || next_skip {
-            next_skip = self.ch == b'\\';
-            value.push(self.ch as char);
+        let mut in_class = false;
+
+        loop {
+            match self.ch {
+                '\0' | '\n' | '\r' => return Err(LexerError::UnterminatedRegex(self.span())),
+                '\\' => {
+                    self.next_char();
+                    if matches!(self.ch, '\0' | '\n' | '\r') {
+                        continue;
+                    }
+                }
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => break,
+                _ => {}
+            }
+            self.next_char();
+        }
+
+        self.next_char();
+
+        let flags_start = (self.offset, self.line, self.column);
+        while self.ch.is_alphanumeric() || self.ch == '_' || self.ch == '$' {
             self.next_char();
         }
+        let flags = &self.source[flags_start.0..self.offset];
+
+        let valid = flags
+            .char_indices()
+            .all(|(i, flag)| "dgimsuvy".contains(flag) && !flags[..i].contains(flag));
+        if !valid {
+            let (start, line, column) = flags_start;
+            return Err(LexerError::InvalidRegexFlags(Span::new(
+                start,
+                self.offset,
+                line,
+                column,
+            )));
+        }
+
+        Ok(Token::Regex(Cow::Borrowed(
+            &self.source[self.start.0..self.offset],
+        )))
+    }
+
+    /// Reads a `"`, `'` or `` ` `` delimited string, decoding JavaScript
+    /// escape sequences. Template literals may span lines but must not
+    /// contain `${...}` interpolation.
+    fn consume_string(&mut self) -> Result<Token<'src>, LexerError> {
+        let quote_type = self.ch;
+        let mut error = None;
+
+        self.next_char();
+
+        // The value borrows from the input until the first escape sequence.
+        let content_start = self.offset;
+        let mut value = None;
+
+        while self.ch != quote_type {
+            match self.ch {
+                '\0' => return Err(LexerError::UnterminatedString(self.span())),
+                '\n' | '\r' if quote_type != '`' => {
+                    return Err(LexerError::UnterminatedString(self.span()))
+                }
+                '\\' => {
+                    let start = (self.offset, self.line, self.column);
+                    let value = value
+                        .get_or_insert_with(|| self.source[content_start..self.offset].to_string());
+                    self.next_char();
+
+                    if let Err(err) = self.consume_escape(value, start) {
+                        error.get_or_insert(err);
+                    }
+                }
+                ch => {
+                    if quote_type == '`' && self.rest().starts_with("${") {
+                        let span = Span::new(self.offset, self.offset + 2, self.line, self.column);
+                        error.get_or_insert(LexerError::TemplateInterpolation(span));
+                    }
+                    if let Some(value) = &mut value {
+                        value.push(ch);
+                    }
+                    self.next_char();
+                }
+            }
+        }
 
+        let value = match value {
+            Some(value) => Cow::Owned(value),
+            None => Cow::Borrowed(&self.source[content_start..self.offset]),
+        };
+        self.next_char();
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(Token::Str(value)),
+        }
+    }
+
+    /// Decodes the escape sequence following a `\` that started at `start`.
+    fn consume_escape(
+        &mut self,
+        value: &mut String,
+        start: (usize, usize, usize),
+    ) -> Result<(), LexerError> {
+        let ch = self.ch;
+        if ch == '\0' {
+            return Ok(());
+        }
         self.next_char();
 
-        Token::Str(value)
+        let decoded = match ch {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            '0' if !self.ch.is_ascii_digit() => '\0',
+            // Line continuations produce nothing.
+            '\r' => {
+                if self.ch == '\n' {
+                    self.next_char();
+                }
+                return Ok(());
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => return Ok(()),
+            'x' => self
+                .consume_code_point(2)
+                .and_then(char::from_u32)
+                .ok_or_else(|| self.invalid_escape(start))?,
+            'u' => self
+                .consume_unicode_escape()
+                .ok_or_else(|| self.invalid_escape(start))?,
+            ch => ch,
+        };
+
+        value.push(decoded);
+
+        Ok(())
     }
 
-    fn consume_ident(&mut self) -> Token {
-        let mut value = String::new();
+    fn invalid_escape(&self, (offset, line, column): (usize, usize, usize)) -> LexerError {
+        LexerError::InvalidEscape(Span::new(offset, self.offset, line, column))
+    }
 
-        while self.ch.is_ascii_alphanumeric() || self.ch == b'_' {
-            value.push(self.ch as char);
+    /// Reads the code point of a `\uXXXX` or `\u{X...}` escape, combining
+    /// surrogate pairs. Lone surrogates decode to U+FFFD.
+    fn consume_unicode_escape(&mut self) -> Option<char> {
+        let code = if self.ch == '{' {
+            self.next_char();
+            let digits = self.rest().find('}').filter(|len| (1..=6).contains(len))?;
+            let code = self.consume_code_point(digits)?;
+            if self.ch != '}' {
+                return None;
+            }
             self.next_char();
+            code
+        } else {
+            self.consume_code_point(4)?
+        };
+
+        if !(0xD800..=0xDBFF).contains(&code) {
+            return char::from_u32(code).or(Some(char::REPLACEMENT_CHARACTER));
         }
 
-        Token::Ident(value)
+        let low = self
+            .rest()
+            .strip_prefix("\\u")
+            .and_then(|rest| rest.get(..4));
+        match low.and_then(|low| u32::from_str_radix(low, 16).ok()) {
+            Some(low) if (0xDC00..=0xDFFF).contains(&low) => {
+                for _ in 0..6 {
+                    self.next_char();
+                }
+                char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00))
+            }
+            _ => Some(char::REPLACEMENT_CHARACTER),
+        }
     }
 
-    fn skip_whitespace(&mut self) {
-        while let b' ' | b'\t' | b'\n' | b'\r' = self.ch {
+    /// Reads exactly `digits` hexadecimal digits.
+    fn consume_code_point(&mut self, digits: usize) -> Option<u32> {
+        let mut code = 0;
+
+        for _ in 0..digits {
+            code = code * 16 + self.ch.to_digit(16)?;
             self.next_char();
         }
+
+        Some(code)
     }
 
-    fn consume_int(&mut self) -> Token {
-        let mut value = String::new();
+    fn consume_ident(&mut self) -> Token<'src> {
+        while self.ch.is_alphanumeric() || self.ch == '_' || self.ch == '$' {
+            self.next_char();
+        }
+
+        match &self.source[self.start.0..self.offset] {
+            "true" => Token::True,
+            "false" => Token::False,
+            ident => Token::Ident(Cow::Borrowed(ident)),
+        }
+    }
+
+    /// Skips whitespace and comments. Doc comments (`/** ... */`) are kept
+    /// and returned as `Token::DocComment`.
+    fn skip_trivia(&mut self) -> Result<(), LexerError> {
+        loop {
+            self.mark_start();
+
+            if self.ch.is_whitespace() || self.ch == '\u{feff}' {
+                self.next_char();
+            } else if self.rest().starts_with("//") {
+                while !matches!(self.ch, '\n' | '\0') {
+                    self.next_char();
+                }
+            } else if self.rest().starts_with("/**/")
+                || (self.rest().starts_with("/*") && !self.rest().starts_with("/**"))
+            {
+                self.consume_block_comment()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Whether a `-` or `.` at the current position begins a numeric literal,
+    /// as in `-5`, `.5` or `-.5`.
+    fn starts_number(&self) -> bool {
+        let rest = self.rest();
+        let rest = rest.strip_prefix('-').unwrap_or(rest);
+        let rest = rest.strip_prefix('.').unwrap_or(rest);
 
-        while self.ch.is_ascii_digit() || self.ch == b'.' {
-            value.push(self.ch as char);
+        rest.starts_with(|ch: char| ch.is_ascii_digit())
+    }
+
+    /// Reads a numeric literal. Every character that could continue the
+    /// literal is consumed so malformed input like `1.2.3` or `0xZ` is
+    /// reported as a whole.
+    fn consume_number(&mut self) -> Result<Token<'src>, LexerError> {
+        if self.ch == '-' {
             self.next_char();
         }
 
-        Token::Int(value)
+        loop {
+            let value = &self.source[self.start.0..self.offset];
+            let exponent_sign = matches!(self.ch, '+' | '-')
+                && value.ends_with(['e', 'E'])
+                && !value.contains(['x', 'X']);
+            let fraction = self.ch == '.' && self.starts_number();
+
+            if self.ch.is_alphanumeric() || self.ch == '_' || exponent_sign || fraction {
+                self.next_char();
+            } else {
+                break;
+            }
+        }
+
+        match self.source[self.start.0..self.offset].parse() {
+            Ok(number) => Ok(Token::Number(number)),
+            Err(_) => Err(LexerError::InvalidNumber(self.span())),
+        }
+    }
+}
+
+impl<'src> Iterator for Lexer<'src> {
+    type Item = LexResult<'src>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let token = self.lex_token();
+        if let Ok(Spanned {
+            node: Token::Eof, ..
+        }) = token
+        {
+            self.done = true;
+        }
+
+        Some(token)
     }
 }
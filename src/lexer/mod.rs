@@ -1,5 +1,12 @@
+mod error;
+#[allow(clippy::module_inception)]
 mod lexer;
+mod number;
+mod span;
 mod token;
 
-pub use lexer::Lexer;
+pub use error::LexerError;
+pub use lexer::{LexResult, Lexer};
+pub use number::{Number, ParseNumberError};
+pub use span::{Span, Spanned};
 pub use token::Token;
@@ -0,0 +1,129 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// Value of a numeric literal.
+///
+/// Integral literals that fit in an `i64` are `Integer`, everything else
+/// (fractions, exponents, very large integers) is `Float`. Literals with an
+/// `n` suffix are `BigInt`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+    BigInt(i128),
+}
+
+impl Number {
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(value) => *value as f64,
+            Number::Float(value) => *value,
+            Number::BigInt(value) => *value as f64,
+        }
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Integer(value) => write!(f, "{value}"),
+            Number::Float(value) => write!(f, "{value}"),
+            Number::BigInt(value) => write!(f, "{value}n"),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Invalid numeric literal")]
+pub struct ParseNumberError;
+
+impl FromStr for Number {
+    type Err = ParseNumberError;
+
+    /// Parses a JavaScript numeric literal: decimal with optional fraction and
+    /// exponent, `0x`/`0o`/`0b` prefixed integers, `_` digit separators, a
+    /// leading `-` and the `n` bigint suffix.
+    fn from_str(literal: &str) -> Result<Self, Self::Err> {
+        let (negative, body) = match literal.strip_prefix('-') {
+            Some(body) => (true, body),
+            None => (false, literal),
+        };
+        let (bigint, body) = match body.strip_suffix('n') {
+            Some(body) => (true, body),
+            None => (false, body),
+        };
+
+        let radix = match body.get(..2).map(str::to_ascii_lowercase).as_deref() {
+            Some("0x") => 16,
+            Some("0o") => 8,
+            Some("0b") => 2,
+            _ => 10,
+        };
+        let body = if radix == 10 { body } else { &body[2..] };
+
+        if !valid_separators(body, radix) {
+            return Err(ParseNumberError);
+        }
+        let digits = body.replace('_', "");
+
+        if radix != 10 {
+            if digits.is_empty() {
+                return Err(ParseNumberError);
+            }
+            let value = u128::from_str_radix(&digits, radix).map_err(|_| ParseNumberError)?;
+            return integer(negative, bigint, value);
+        }
+
+        if !digits.starts_with(|ch: char| ch.is_ascii_digit() || ch == '.') {
+            return Err(ParseNumberError);
+        }
+        // Legacy octal literals like `017` are not allowed.
+        if digits.len() > 1 && digits.starts_with('0') && digits.as_bytes()[1].is_ascii_digit() {
+            return Err(ParseNumberError);
+        }
+
+        if digits.chars().all(|ch| ch.is_ascii_digit()) {
+            let value = digits.parse::<u128>().map_err(|_| ParseNumberError)?;
+            return integer(negative, bigint, value);
+        }
+
+        if bigint {
+            return Err(ParseNumberError);
+        }
+        let value = digits.parse::<f64>().map_err(|_| ParseNumberError)?;
+
+        Ok(Number::Float(if negative { -value } else { value }))
+    }
+}
+
+fn integer(negative: bool, bigint: bool, value: u128) -> Result<Number, ParseNumberError> {
+    if bigint {
+        let value = i128::try_from(value).map_err(|_| ParseNumberError)?;
+        return Ok(Number::BigInt(if negative { -value } else { value }));
+    }
+
+    let signed = i64::try_from(value)
+        .ok()
+        .map(|value| if negative { -value } else { value });
+
+    Ok(match signed {
+        Some(value) => Number::Integer(value),
+        None if negative => Number::Float(-(value as f64)),
+        None => Number::Float(value as f64),
+    })
+}
+
+/// `_` is only allowed between two digits.
+fn valid_separators(body: &str, radix: u32) -> bool {
+    let chars: Vec<char> = body.chars().collect();
+
+    chars.iter().enumerate().all(|(i, ch)| {
+        *ch != '_'
+            || (i > 0
+                && i + 1 < chars.len()
+                && chars[i - 1].is_digit(radix)
+                && chars[i + 1].is_digit(radix))
+    })
+}
@@ -1,11 +1,16 @@
+use std::borrow::Cow;
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq)]
-pub enum Token {
+use super::Number;
+
+/// A token produced by the `Lexer`. Identifiers, strings and doc comments
+/// borrow from the source unless escapes had to be decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'src> {
     Illegal,
     Eof,
-    Ident(String),
-    Int(String),
+    Ident(Cow<'src, str>),
+    Number(Number),
     Comma,
     LRound,
     RRound,
@@ -15,17 +20,57 @@ pub enum Token {
     RSquare,
     True,
     False,
-    Str(String),
+    Str(Cow<'src, str>),
+    /// A `/pattern/flags` literal exactly as written, slashes included.
+    Regex(Cow<'src, str>),
     Dot,
     Colon,
+    Equals,
+    Semicolon,
+    /// ASCII punctuation the schema grammar doesn't use, like `>` or `+`.
+    /// It is only valid inside skipped method arguments such as `.refine()`.
+    Punct(char),
+    /// Contents of a `/** ... */` comment, attached by the parser to the
+    /// following object key.
+    DocComment(Cow<'src, str>),
+}
+
+impl Token<'_> {
+    /// Detaches the token from the source it was read from.
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Illegal => Token::Illegal,
+            Token::Eof => Token::Eof,
+            Token::Ident(value) => Token::Ident(Cow::Owned(value.into_owned())),
+            Token::Number(value) => Token::Number(value),
+            Token::Comma => Token::Comma,
+            Token::LRound => Token::LRound,
+            Token::RRound => Token::RRound,
+            Token::LCurly => Token::LCurly,
+            Token::RCurly => Token::RCurly,
+            Token::LSquare => Token::LSquare,
+            Token::RSquare => Token::RSquare,
+            Token::True => Token::True,
+            Token::False => Token::False,
+            Token::Str(value) => Token::Str(Cow::Owned(value.into_owned())),
+            Token::Regex(value) => Token::Regex(Cow::Owned(value.into_owned())),
+            Token::Dot => Token::Dot,
+            Token::Colon => Token::Colon,
+            Token::Equals => Token::Equals,
+            Token::Semicolon => Token::Semicolon,
+            Token::Punct(ch) => Token::Punct(ch),
+            Token::DocComment(value) => Token::DocComment(Cow::Owned(value.into_owned())),
+        }
+    }
 }
 
-impl Display for Token {
+impl Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Token::Illegal => write!(f, ""),
             Token::Eof => write!(f, "\0"),
-            Token::Ident(value) | Token::Int(value) | Token::Str(value) => write!(f, "{value}"),
+            Token::Ident(value) | Token::Str(value) | Token::Regex(value) => write!(f, "{value}"),
+            Token::Number(value) => write!(f, "{value}"),
             Token::Comma => write!(f, ","),
             Token::LRound => write!(f, "("),
             Token::RRound => write!(f, ")"),
@@ -37,11 +82,15 @@ impl Display for Token {
             Token::False => write!(f, "false"),
             Token::Dot => write!(f, "."),
             Token::Colon => write!(f, ":"),
+            Token::Equals => write!(f, "="),
+            Token::Semicolon => write!(f, ";"),
+            Token::Punct(ch) => write!(f, "{ch}"),
+            Token::DocComment(value) => write!(f, "/** {value} */"),
         }
     }
 }
 
-impl From<char> for Token {
+impl From<char> for Token<'static> {
     fn from(ch: char) -> Self {
         match ch {
             '(' => Self::LRound,
@@ -53,26 +102,29 @@ impl From<char> for Token {
             ']' => Self::RSquare,
             '.' => Self::Dot,
             ':' => Self::Colon,
+            '=' => Self::Equals,
+            ';' => Self::Semicolon,
             '\0' => Self::Eof,
+            ch if ch.is_ascii_punctuation() => Self::Punct(ch),
             _ => Self::Illegal,
         }
     }
 }
 
-impl From<String> for Token {
+impl From<String> for Token<'static> {
     fn from(value: String) -> Self {
         match value.as_str() {
             "true" => Self::True,
             "false" => Self::False,
             _ => {
-                if value.chars().all(|b| b.is_ascii_digit() || b == '.') {
-                    Self::Int(value)
+                if let Ok(number) = value.parse() {
+                    Self::Number(number)
                 } else if (value.starts_with('"') && value.ends_with('"'))
                     || (value.starts_with('\'') && value.ends_with('\''))
                 {
-                    Self::Str(value)
+                    Self::Str(Cow::Owned(value))
                 } else {
-                    Self::Ident(value)
+                    Self::Ident(Cow::Owned(value))
                 }
             }
         }
@@ -1,7 +1,15 @@
 pub mod compiler;
+pub mod diagnostics;
+pub mod error;
 pub mod lexer;
 pub mod parser;
+pub mod regex;
 pub mod syntax_tree;
 
 pub use compiler::get_syntax_tree;
+pub use compiler::get_syntax_tree_recovering;
 pub use compiler::to_json;
+pub use compiler::to_json_variants;
+pub use compiler::to_json_with;
+pub use compiler::JsonOptions;
+pub use error::Error;
@@ -1,8 +1,4 @@
-use rust_ts_json_compiler::{
-    lexer::{Lexer, Token},
-    syntax_tree::SyntaxTree,
-    to_json,
-};
+use rust_ts_json_compiler::{lexer::Lexer, syntax_tree::SyntaxTree, to_json};
 
 pub fn main() -> anyhow::Result<()> {
     // let schema = "z.coerce.number()";
@@ -33,23 +29,11 @@ products: z.array(
     .union([z.array(z.coerce.number().int()), z.coerce.number().int()])
 })
 })";
-    let mut lx = Lexer::new(schema);
-    let mut tokens = Vec::new();
+    let mut tree = SyntaxTree::new(Lexer::new(schema));
 
-    loop {
-        let token = lx.next_token();
-        if token == Token::Eof {
-            break;
-        }
+    let zod = tree.parse()?;
 
-        tokens.push(token);
-    }
-
-    // println!("{:?}", tokens);
-
-    let mut tree = SyntaxTree::new(tokens.into_iter().peekable());
-
-    println!("{}", to_json(&tree.parse().unwrap()));
+    println!("{}", to_json(&zod));
 
     Ok(())
 }
@@ -3,5 +3,5 @@ use crate::lexer::Token;
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct Parser {
-    tokens: Vec<Token>,
+    tokens: Vec<Token<'static>>,
 }
@@ -0,0 +1,954 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RegexError {
+    #[error("Unmatched parenthesis")]
+    UnmatchedParen,
+
+    #[error("Unterminated character class")]
+    UnterminatedClass,
+
+    #[error("Nothing to repeat")]
+    NothingToRepeat,
+
+    #[error("Numbers out of order in quantifier")]
+    InvalidQuantifier,
+
+    #[error("Range out of order in character class")]
+    InvalidRange,
+
+    #[error("Invalid group")]
+    InvalidGroup,
+
+    #[error("Invalid escape")]
+    InvalidEscape,
+
+    #[error("Invalid backreference")]
+    InvalidBackreference,
+
+    #[error("Pattern only matches strings over 10000 characters long")]
+    RepetitionTooLarge,
+
+    #[error("Unsupported pattern, no matching example could be built")]
+    Unsupported,
+}
+
+/// Longest string a pattern may require, and the longest example built, so
+/// examples stay small.
+pub const MAX_EXAMPLE_LENGTH: usize = 10_000;
+/// Stack given to the matcher per character of text and level of nesting in
+/// the pattern, as it recurses for each repetition of a group.
+const STACK_PER_CHAR: usize = 4096;
+
+/// A parsed JavaScript regular expression, detailed enough to build a string
+/// that matches it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    node: Node,
+    /// Names of `(?<name>...)` groups and their capture index.
+    names: Vec<(String, usize)>,
+    groups: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(char),
+    /// `.`
+    Any,
+    Class(Class),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    /// A group, with its capture index unless it is `(?:...)`.
+    Group(Box<Node>, Option<usize>),
+    Repeat(Box<Node>, u32, Option<u32>),
+    Backreference(Backreference),
+    /// Anchors, word boundaries and lookarounds, which match no characters.
+    Assertion(Assertion),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Assertion {
+    Start,
+    End,
+    WordBoundary {
+        negated: bool,
+    },
+    Lookaround {
+        node: Box<Node>,
+        ahead: bool,
+        negated: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Backreference {
+    Index(usize),
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Class {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+}
+
+const DIGITS: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+const SPACE: &[(char, char)] = &[
+    (' ', ' '),
+    ('\t', '\r'),
+    ('\u{a0}', '\u{a0}'),
+    ('\u{2028}', '\u{2029}'),
+    ('\u{feff}', '\u{feff}'),
+];
+/// Characters tried, in order, for `.` and negated classes.
+const PRINTABLE: &[(char, char)] = &[
+    ('a', 'z'),
+    ('A', 'Z'),
+    ('0', '9'),
+    ('!', '/'),
+    (':', '@'),
+    ('[', '`'),
+    ('{', '~'),
+    (' ', ' '),
+];
+
+/// Parses the source of a `/pattern/` literal, without the slashes.
+pub fn parse(pattern: &str) -> Result<Pattern, RegexError> {
+    let mut parser = Parser {
+        chars: pattern.chars().collect(),
+        pos: 0,
+        groups: 0,
+        names: Vec::new(),
+        backreferences: Vec::new(),
+    };
+
+    let node = parser.parse_alternation()?;
+    if parser.pos < parser.chars.len() {
+        return Err(RegexError::UnmatchedParen);
+    }
+    if min_length(&node) > MAX_EXAMPLE_LENGTH {
+        return Err(RegexError::RepetitionTooLarge);
+    }
+
+    for backreference in &parser.backreferences {
+        let valid = match backreference {
+            Backreference::Index(index) => *index <= parser.groups,
+            Backreference::Name(name) => parser.names.iter().any(|(other, _)| other == name),
+        };
+        if !valid {
+            return Err(RegexError::InvalidBackreference);
+        }
+    }
+
+    Ok(Pattern {
+        node,
+        names: parser.names,
+        groups: parser.groups,
+    })
+}
+
+impl Pattern {
+    /// Builds a string the pattern matches. Alternations take their first
+    /// branch, optional parts are included once and classes are walked in
+    /// order, so `^[A-Z]{3}-\d{4}$` gives `ABC-0123`. Returns `None` when
+    /// the result doesn't match, e.g. when lookarounds can't be satisfied
+    /// this way.
+    pub fn example(&self) -> Option<String> {
+        self.example_with_length(0, usize::MAX)
+    }
+
+    /// Like `example`, but between `min` and `max` characters long. Extra
+    /// characters come from repeating quantified parts, and optional parts
+    /// are left out to stay short. `min` is capped at `MAX_EXAMPLE_LENGTH`.
+    pub fn example_with_length(&self, min: usize, max: usize) -> Option<String> {
+        if min > MAX_EXAMPLE_LENGTH {
+            return None;
+        }
+        let fits = |example: &String| (min..=max).contains(&example.chars().count());
+
+        let mut example = self.generate(&self.node, false, 0);
+        let len = example.0.chars().count();
+        if len > max {
+            example = self.generate(&self.node, true, 0);
+        }
+        let len = example.0.chars().count();
+        if len < min {
+            example = self.generate(&self.node, len > max, min - len);
+        }
+        if !fits(&example.0) {
+            return None;
+        }
+        if !has_lookaround(&self.node) {
+            return Some(example.0).filter(|example| self.is_match(example));
+        }
+
+        [false, true]
+            .into_iter()
+            .filter_map(|at_end| self.satisfy_lookarounds(&example, at_end))
+            .find(fits)
+    }
+
+    /// Whether `text` contains a match, like `RegExp.prototype.test`.
+    pub fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let search = || {
+            let matcher = Matcher {
+                input: &chars,
+                names: &self.names,
+            };
+            (0..=chars.len()).any(|start| {
+                let mut captures = vec![None; self.groups + 1];
+                matcher.matches(&self.node, start, &mut captures, &mut |_, _| true)
+            })
+        };
+        if chars.len() < 100 {
+            return search();
+        }
+
+        // Long texts get a thread with room for the matcher's recursion.
+        std::thread::scope(|scope| {
+            std::thread::Builder::new()
+                .stack_size(chars.len() * depth(&self.node) * STACK_PER_CHAR)
+                .spawn_scoped(scope, search)
+                .is_ok_and(|thread| thread.join().unwrap_or(false))
+        })
+    }
+
+    /// Generates an example of `node`, along with the positive lookarounds
+    /// it passed and the character offsets they were at.
+    fn generate<'a>(
+        &'a self,
+        node: &'a Node,
+        shrink: bool,
+        extra: usize,
+    ) -> (String, Vec<Lookaround<'a>>) {
+        let mut generator = Generator {
+            names: &self.names,
+            captures: vec![None; self.groups + 1],
+            shrink,
+            extra,
+            lookarounds: vec![],
+        };
+        let mut example = String::new();
+        generator.generate(node, 0, &mut example);
+
+        (example, generator.lookarounds)
+    }
+
+    /// Writes an example of each positive lookahead over the text after it,
+    /// so `^(?=.*[A-Z])(?=.*\d).{8,}$` gives `A0aaaaaa`, or over the end of
+    /// the text with `at_end`, and inserts one of each positive lookbehind
+    /// before it. Checks that the result matches.
+    fn satisfy_lookarounds(
+        &self,
+        (example, lookarounds): &(String, Vec<Lookaround>),
+        at_end: bool,
+    ) -> Option<String> {
+        let mut chars: Vec<char> = example.chars().collect();
+        let (mut offset, mut inserted) = (0, 0);
+        let mut tail = chars.len();
+
+        for &(position, node, ahead) in lookarounds {
+            let part: Vec<char> = self.generate(node, true, 0).0.chars().collect();
+            let position = position + inserted;
+            if ahead && at_end {
+                let start = tail.saturating_sub(part.len()).max(position);
+                tail = start;
+                chars.splice(start..(start + part.len()).min(chars.len()), part);
+            } else if ahead {
+                offset = offset.max(position);
+                let end = offset + part.len();
+                chars.splice(offset..end.min(chars.len()), part);
+                offset = end;
+            } else {
+                inserted += part.len();
+                offset = offset.max(position) + part.len();
+                chars.splice(position..position, part);
+            }
+        }
+
+        let example: String = chars.into_iter().collect();
+        self.is_match(&example).then_some(example)
+    }
+}
+
+/// Whether `node` requires a character from an empty class, like `[]`.
+fn never_matches(node: &Node) -> bool {
+    match node {
+        Node::Class(class) => class.pick(0).is_none(),
+        Node::Concat(nodes) => nodes.iter().any(never_matches),
+        Node::Alternation(branches) => branches.iter().all(never_matches),
+        Node::Group(node, _) => never_matches(node),
+        Node::Repeat(node, min, _) => *min > 0 && never_matches(node),
+        _ => false,
+    }
+}
+
+/// How deeply `node` nests.
+fn depth(node: &Node) -> usize {
+    1 + match node {
+        Node::Concat(nodes) | Node::Alternation(nodes) => {
+            nodes.iter().map(depth).max().unwrap_or(0)
+        }
+        Node::Group(node, _) | Node::Repeat(node, ..) => depth(node),
+        Node::Assertion(Assertion::Lookaround { node, .. }) => depth(node),
+        _ => 0,
+    }
+}
+
+fn has_lookaround(node: &Node) -> bool {
+    match node {
+        Node::Assertion(Assertion::Lookaround { .. }) => true,
+        Node::Concat(nodes) | Node::Alternation(nodes) => nodes.iter().any(has_lookaround),
+        Node::Group(node, _) | Node::Repeat(node, ..) => has_lookaround(node),
+        _ => false,
+    }
+}
+
+/// Length of the shortest string `node` can match, ignoring backreferences.
+fn min_length(node: &Node) -> usize {
+    match node {
+        Node::Literal(_) | Node::Any | Node::Class(_) => 1,
+        Node::Concat(nodes) => nodes
+            .iter()
+            .fold(0, |total, node| total.saturating_add(min_length(node))),
+        Node::Alternation(nodes) => nodes.iter().map(min_length).min().unwrap_or(0),
+        Node::Group(node, _) => min_length(node),
+        Node::Repeat(node, min, _) => min_length(node).saturating_mul(*min as usize),
+        Node::Backreference(_) | Node::Assertion(_) => 0,
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    groups: usize,
+    names: Vec<(String, usize)>,
+    /// Checked once the whole pattern is parsed, as they may refer forward.
+    backreferences: Vec<Backreference>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek();
+        self.pos += 1;
+        ch
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        let matches = expected
+            .chars()
+            .enumerate()
+            .all(|(i, ch)| self.chars.get(self.pos + i) == Some(&ch));
+        if matches {
+            self.pos += expected.chars().count();
+        }
+        matches
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.eat("|") {
+            branches.push(self.parse_concat()?);
+        }
+
+        Ok(match branches.len() {
+            1 => branches.pop().unwrap(),
+            _ => Node::Alternation(branches),
+        })
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, RegexError> {
+        let mut nodes = vec![];
+
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+
+        Ok(Node::Concat(nodes))
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, RegexError> {
+        match self.next() {
+            Some('^') => Ok(Node::Assertion(Assertion::Start)),
+            Some('$') => Ok(Node::Assertion(Assertion::End)),
+            Some('.') => Ok(Node::Any),
+            Some('[') => self.parse_class(),
+            Some('(') => self.parse_group(),
+            Some('\\') => self.parse_escape(),
+            Some('*' | '+' | '?') => Err(RegexError::NothingToRepeat),
+            Some(ch) => Ok(Node::Literal(ch)),
+            None => Err(RegexError::NothingToRepeat),
+        }
+    }
+
+    fn parse_group(&mut self) -> Result<Node, RegexError> {
+        let capture = if self.eat("?:") {
+            None
+        } else if let Some((ahead, negated)) = self.parse_lookaround() {
+            let node = self.parse_alternation()?;
+            if !self.eat(")") {
+                return Err(RegexError::UnmatchedParen);
+            }
+            return Ok(Node::Assertion(Assertion::Lookaround {
+                node: Box::new(node),
+                ahead,
+                negated,
+            }));
+        } else if self.eat("?<") {
+            let name = self.parse_group_name()?;
+            self.groups += 1;
+            self.names.push((name, self.groups));
+            Some(self.groups)
+        } else if self.peek() == Some('?') {
+            return Err(RegexError::InvalidGroup);
+        } else {
+            self.groups += 1;
+            Some(self.groups)
+        };
+
+        let node = self.parse_alternation()?;
+        if !self.eat(")") {
+            return Err(RegexError::UnmatchedParen);
+        }
+
+        Ok(Node::Group(Box::new(node), capture))
+    }
+
+    /// Reads `?=`, `?!`, `?<=` or `?<!` after `(`, returning whether it
+    /// looks ahead and whether it is negated.
+    fn parse_lookaround(&mut self) -> Option<(bool, bool)> {
+        [
+            ("?=", true, false),
+            ("?!", true, true),
+            ("?<=", false, false),
+            ("?<!", false, true),
+        ]
+        .into_iter()
+        .find(|(prefix, ..)| self.eat(prefix))
+        .map(|(_, ahead, negated)| (ahead, negated))
+    }
+
+    /// Reads `name>` after `(?<` or `\k<`.
+    fn parse_group_name(&mut self) -> Result<String, RegexError> {
+        let mut name = String::new();
+
+        loop {
+            match self.next() {
+                Some('>') if !name.is_empty() => return Ok(name),
+                Some(ch) if ch.is_alphanumeric() || ch == '_' || ch == '$' => name.push(ch),
+                _ => return Err(RegexError::InvalidGroup),
+            }
+        }
+    }
+
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, RegexError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.parse_braces() {
+                Some(bounds) => bounds,
+                // Not a quantifier, so a literal `{`.
+                None => return Ok(atom),
+            },
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        // Lazy quantifiers match the same strings.
+        self.eat("?");
+
+        if max.is_some_and(|max| max < min) {
+            return Err(RegexError::InvalidQuantifier);
+        }
+        if matches!(self.peek(), Some('*' | '+' | '?')) {
+            return Err(RegexError::NothingToRepeat);
+        }
+
+        Ok(Node::Repeat(Box::new(atom), min, max))
+    }
+
+    /// Parses `{n}`, `{n,}` or `{n,m}`, leaving the position on its `}`.
+    fn parse_braces(&mut self) -> Option<(u32, Option<u32>)> {
+        let rest: String = self.chars[self.pos..].iter().collect();
+        let end = rest.find('}')?;
+        let body = &rest[1..end];
+        if !body.starts_with(|ch: char| ch.is_ascii_digit()) {
+            return None;
+        }
+
+        let bounds = match body.split_once(',') {
+            None => {
+                let count = body.parse().ok()?;
+                (count, Some(count))
+            }
+            Some((min, "")) => (min.parse().ok()?, None),
+            Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+        };
+
+        self.pos += rest[..end].chars().count();
+        Some(bounds)
+    }
+
+    fn parse_escape(&mut self) -> Result<Node, RegexError> {
+        match self.peek() {
+            Some(ch @ ('b' | 'B')) => {
+                self.pos += 1;
+                Ok(Node::Assertion(Assertion::WordBoundary {
+                    negated: ch == 'B',
+                }))
+            }
+            Some('1'..='9') => {
+                let mut index = 0usize;
+                while let Some(digit) = self.peek().and_then(|ch| ch.to_digit(10)) {
+                    index = index * 10 + digit as usize;
+                    self.pos += 1;
+                }
+                self.backreferences.push(Backreference::Index(index));
+                Ok(Node::Backreference(Backreference::Index(index)))
+            }
+            Some('k') if self.chars.get(self.pos + 1) == Some(&'<') => {
+                self.pos += 2;
+                let name = self.parse_group_name()?;
+                self.backreferences.push(Backreference::Name(name.clone()));
+                Ok(Node::Backreference(Backreference::Name(name)))
+            }
+            _ => match self.parse_class_escape()? {
+                ClassEscape::Char(ch) => Ok(Node::Literal(ch)),
+                ClassEscape::Class(class) => Ok(Node::Class(class)),
+            },
+        }
+    }
+
+    /// Parses an escape that is valid both inside and outside a class.
+    fn parse_class_escape(&mut self) -> Result<ClassEscape, RegexError> {
+        let class = |negated, ranges: &[(char, char)]| {
+            Ok(ClassEscape::Class(Class {
+                negated,
+                ranges: ranges.to_vec(),
+            }))
+        };
+
+        match self.next().ok_or(RegexError::InvalidEscape)? {
+            'd' => class(false, DIGITS),
+            'D' => class(true, DIGITS),
+            'w' => class(false, WORD),
+            'W' => class(true, WORD),
+            's' => class(false, SPACE),
+            'S' => class(true, SPACE),
+            'p' | 'P' if self.peek() == Some('{') => {
+                let negated = self.chars[self.pos - 1] == 'P';
+                let end = self.chars[self.pos..]
+                    .iter()
+                    .position(|ch| *ch == '}')
+                    .ok_or(RegexError::InvalidEscape)?;
+                let property: String = self.chars[self.pos + 1..self.pos + end].iter().collect();
+                self.pos += end + 1;
+
+                // Only the common properties are told apart, anything else is
+                // assumed to contain letters.
+                match property.as_str() {
+                    "N" | "Nd" | "Number" | "Decimal_Number" => class(negated, DIGITS),
+                    "Lu" | "Uppercase_Letter" | "Uppercase" => class(negated, &[('A', 'Z')]),
+                    _ => class(negated, &[('a', 'z'), ('A', 'Z')]),
+                }
+            }
+            'n' => Ok(ClassEscape::Char('\n')),
+            'r' => Ok(ClassEscape::Char('\r')),
+            't' => Ok(ClassEscape::Char('\t')),
+            'f' => Ok(ClassEscape::Char('\u{c}')),
+            'v' => Ok(ClassEscape::Char('\u{b}')),
+            '0' => Ok(ClassEscape::Char('\0')),
+            'c' => match self.next() {
+                Some(letter) if letter.is_ascii_alphabetic() => {
+                    Ok(ClassEscape::Char(char::from(letter as u8 % 32)))
+                }
+                _ => Err(RegexError::InvalidEscape),
+            },
+            'x' => self.parse_hex(2).map(ClassEscape::Char),
+            'u' if self.eat("{") => {
+                let end = self.chars[self.pos..]
+                    .iter()
+                    .position(|ch| *ch == '}')
+                    .ok_or(RegexError::InvalidEscape)?;
+                let digits = self.parse_hex(end)?;
+                self.pos += 1;
+                Ok(ClassEscape::Char(digits))
+            }
+            'u' => self.parse_hex(4).map(ClassEscape::Char),
+            ch => Ok(ClassEscape::Char(ch)),
+        }
+    }
+
+    fn parse_hex(&mut self, len: usize) -> Result<char, RegexError> {
+        let digits: String = self
+            .chars
+            .get(self.pos..self.pos + len)
+            .ok_or(RegexError::InvalidEscape)?
+            .iter()
+            .collect();
+        self.pos += len;
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(RegexError::InvalidEscape)
+    }
+
+    fn parse_class(&mut self) -> Result<Node, RegexError> {
+        let negated = self.eat("^");
+        let mut ranges = vec![];
+        // Escapes like `\D` inside a class, matched as a union.
+        let mut nested = vec![];
+
+        loop {
+            let start = match self.next() {
+                Some(']') => break,
+                Some('\\') => match self.parse_class_escape()? {
+                    ClassEscape::Char(ch) => ch,
+                    ClassEscape::Class(class) => {
+                        nested.push(class);
+                        continue;
+                    }
+                },
+                Some(ch) => ch,
+                None => return Err(RegexError::UnterminatedClass),
+            };
+
+            let is_range = self.peek() == Some('-')
+                && !matches!(self.chars.get(self.pos + 1), Some(']') | None);
+            if !is_range {
+                ranges.push((start, start));
+                continue;
+            }
+            self.pos += 1;
+
+            let end = match self.next() {
+                Some('\\') => match self.parse_class_escape()? {
+                    ClassEscape::Char(ch) => ch,
+                    // `[a-\d]` is a literal `-` next to both.
+                    ClassEscape::Class(class) => {
+                        ranges.push((start, start));
+                        ranges.push(('-', '-'));
+                        nested.push(class);
+                        continue;
+                    }
+                },
+                Some(ch) => ch,
+                None => return Err(RegexError::UnterminatedClass),
+            };
+            if end < start {
+                return Err(RegexError::InvalidRange);
+            }
+            ranges.push((start, end));
+        }
+
+        // Fold positive nested classes in; negated ones are approximated by
+        // the printable characters they allow.
+        for class in nested {
+            match class.negated {
+                false => ranges.extend(class.ranges),
+                true => ranges.extend(
+                    printable()
+                        .filter(|ch| !class.contains(*ch))
+                        .map(|ch| (ch, ch)),
+                ),
+            }
+        }
+
+        Ok(Node::Class(Class { negated, ranges }))
+    }
+}
+
+enum ClassEscape {
+    Char(char),
+    Class(Class),
+}
+
+impl Class {
+    fn contains(&self, ch: char) -> bool {
+        let listed = self
+            .ranges
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&ch));
+
+        listed != self.negated
+    }
+
+    /// The `index`-th member of the class, wrapping around.
+    fn pick(&self, index: usize) -> Option<char> {
+        let members: Vec<char> = if self.negated {
+            printable().filter(|ch| self.contains(*ch)).collect()
+        } else {
+            // Large ranges only contribute their start.
+            self.ranges
+                .iter()
+                .flat_map(|(start, end)| (*start..=*end).take(26))
+                .collect()
+        };
+
+        match members.len() {
+            0 => None,
+            len => Some(members[index % len]),
+        }
+    }
+}
+
+fn printable() -> impl Iterator<Item = char> {
+    PRINTABLE.iter().flat_map(|(start, end)| *start..=*end)
+}
+
+struct Generator<'a> {
+    names: &'a [(String, usize)],
+    captures: Vec<Option<String>>,
+    /// Leave out optional parts instead of including them once.
+    shrink: bool,
+    /// Characters still wanted beyond the minimum, added by repeating
+    /// quantified parts.
+    extra: usize,
+    /// Positive lookarounds passed.
+    lookarounds: Vec<Lookaround<'a>>,
+}
+
+/// A positive lookaround's character offset, pattern and whether it looks
+/// ahead.
+type Lookaround<'a> = (usize, &'a Node, bool);
+
+impl<'a> Generator<'a> {
+    /// Appends text matching `node` to `out`. `index` counts repetitions of
+    /// the enclosing quantifier so repeated classes vary, e.g. `ABC`.
+    fn generate(&mut self, node: &'a Node, index: usize, out: &mut String) {
+        match node {
+            Node::Literal(ch) => out.push(*ch),
+            Node::Any => out.push(PRINTABLE[0].0),
+            Node::Class(class) => out.extend(class.pick(index)),
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.generate(node, index, out);
+                }
+            }
+            Node::Alternation(branches) => {
+                let branch = branches.iter().find(|branch| !never_matches(branch));
+                self.generate(branch.unwrap_or(&branches[0]), index, out)
+            }
+            Node::Group(node, capture) => {
+                let start = out.len();
+                self.generate(node, index, out);
+                if let Some(capture) = capture {
+                    self.captures[*capture] = Some(out[start..].to_string());
+                }
+            }
+            Node::Repeat(node, min, max) => {
+                let count = match (min, max) {
+                    (0, Some(0)) => 0,
+                    (0, _) if self.shrink => 0,
+                    (0, _) => 1,
+                    (min, _) => *min as usize,
+                };
+                for index in 0..count {
+                    self.generate(node, index, out);
+                }
+
+                let max = max.map_or(usize::MAX, |max| max as usize);
+                let mut index = count;
+                while self.extra > 0 && index < max {
+                    let len = out.len();
+                    self.generate(node, index, out);
+                    let added = out[len..].chars().count();
+                    if added == 0 {
+                        break;
+                    }
+                    self.extra = self.extra.saturating_sub(added);
+                    index += 1;
+                }
+            }
+            Node::Backreference(backreference) => {
+                let capture = match backreference {
+                    Backreference::Index(index) => Some(*index),
+                    Backreference::Name(name) => self
+                        .names
+                        .iter()
+                        .find(|(other, _)| other == name)
+                        .map(|(_, index)| *index),
+                };
+                if let Some(Some(text)) = capture.and_then(|index| self.captures.get(index)) {
+                    out.push_str(text);
+                }
+            }
+            // Lookarounds are satisfied afterwards, see `satisfy_lookarounds`.
+            Node::Assertion(Assertion::Lookaround {
+                node,
+                ahead,
+                negated: false,
+            }) => self.lookarounds.push((out.chars().count(), node, *ahead)),
+            Node::Assertion(_) => {}
+        }
+    }
+}
+
+type Captures = Vec<Option<(usize, usize)>>;
+
+/// Whether the single character `node` matches `ch`.
+fn matches_char(node: &Node, ch: char) -> bool {
+    match node {
+        Node::Literal(literal) => *literal == ch,
+        Node::Any => !matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}'),
+        Node::Class(class) => class.contains(ch),
+        _ => false,
+    }
+}
+
+/// A backtracking matcher, used to check examples. `matches` calls `next` with the end of each way `node` can
+/// match from `pos` until it returns `true`.
+struct Matcher<'a> {
+    input: &'a [char],
+    names: &'a [(String, usize)],
+}
+
+impl Matcher<'_> {
+    fn matches(
+        &self,
+        node: &Node,
+        pos: usize,
+        captures: &mut Captures,
+        next: &mut dyn FnMut(usize, &mut Captures) -> bool,
+    ) -> bool {
+        match node {
+            Node::Literal(_) | Node::Any | Node::Class(_) => {
+                self.input
+                    .get(pos)
+                    .is_some_and(|ch| matches_char(node, *ch))
+                    && next(pos + 1, captures)
+            }
+            Node::Concat(nodes) => self.matches_all(nodes, pos, captures, next),
+            Node::Alternation(branches) => branches.iter().any(|branch| {
+                let saved = captures.clone();
+                self.matches(branch, pos, captures, next) || {
+                    *captures = saved;
+                    false
+                }
+            }),
+            Node::Group(node, capture) => {
+                self.matches(node, pos, captures, &mut |end, captures| {
+                    let Some(index) = *capture else {
+                        return next(end, captures);
+                    };
+                    let saved = captures[index];
+                    captures[index] = Some((pos, end));
+                    next(end, captures) || {
+                        captures[index] = saved;
+                        false
+                    }
+                })
+            }
+            // Repeated characters are counted up front rather than recursing
+            // for each one.
+            Node::Repeat(node, min, max)
+                if matches!(**node, Node::Literal(_) | Node::Any | Node::Class(_)) =>
+            {
+                let max = max.map_or(usize::MAX, |max| max as usize);
+                let count = self.input[pos..]
+                    .iter()
+                    .take(max)
+                    .take_while(|ch| matches_char(node, **ch))
+                    .count();
+                (*min as usize..=count)
+                    .rev()
+                    .any(|count| next(pos + count, captures))
+            }
+            Node::Repeat(node, min, max) => {
+                self.matches_repeat(node, (*min, *max), 0, pos, captures, next)
+            }
+            Node::Backreference(backreference) => {
+                let index = match backreference {
+                    Backreference::Index(index) => Some(*index),
+                    Backreference::Name(name) => self
+                        .names
+                        .iter()
+                        .find(|(other, _)| other == name)
+                        .map(|(_, index)| *index),
+                };
+                match index.and_then(|index| captures.get(index).copied().flatten()) {
+                    Some((start, end)) => {
+                        let text = &self.input[start..end];
+                        self.input[pos..].starts_with(text) && next(pos + text.len(), captures)
+                    }
+                    None => next(pos, captures),
+                }
+            }
+            Node::Assertion(Assertion::Start) => pos == 0 && next(pos, captures),
+            Node::Assertion(Assertion::End) => pos == self.input.len() && next(pos, captures),
+            Node::Assertion(Assertion::WordBoundary { negated }) => {
+                let is_word = |pos: Option<usize>| {
+                    pos.and_then(|pos| self.input.get(pos))
+                        .is_some_and(|ch| ch.is_ascii_alphanumeric() || *ch == '_')
+                };
+                let boundary = is_word(pos.checked_sub(1)) != is_word(Some(pos));
+                boundary != *negated && next(pos, captures)
+            }
+            Node::Assertion(Assertion::Lookaround {
+                node,
+                ahead,
+                negated,
+            }) => {
+                let found = if *ahead {
+                    self.matches(node, pos, &mut captures.clone(), &mut |_, _| true)
+                } else {
+                    (0..=pos).any(|start| {
+                        self.matches(node, start, &mut captures.clone(), &mut |end, _| end == pos)
+                    })
+                };
+                found != *negated && next(pos, captures)
+            }
+        }
+    }
+
+    fn matches_all(
+        &self,
+        nodes: &[Node],
+        pos: usize,
+        captures: &mut Captures,
+        next: &mut dyn FnMut(usize, &mut Captures) -> bool,
+    ) -> bool {
+        match nodes.split_first() {
+            None => next(pos, captures),
+            Some((first, rest)) => self.matches(first, pos, captures, &mut |end, captures| {
+                self.matches_all(rest, end, captures, next)
+            }),
+        }
+    }
+
+    /// Greedily matches `node` again after `count` repetitions, then tries
+    /// stopping.
+    fn matches_repeat(
+        &self,
+        node: &Node,
+        (min, max): (u32, Option<u32>),
+        count: u32,
+        pos: usize,
+        captures: &mut Captures,
+        next: &mut dyn FnMut(usize, &mut Captures) -> bool,
+    ) -> bool {
+        if max.is_none_or(|max| count < max) {
+            let more = self.matches(node, pos, captures, &mut |end, captures| {
+                // An empty repetition can't make progress.
+                (end != pos || count < min)
+                    && self.matches_repeat(node, (min, max), count + 1, end, captures, next)
+            });
+            if more {
+                return true;
+            }
+        }
+
+        count >= min && next(pos, captures)
+    }
+}
@@ -0,0 +1,131 @@
+/// Bounds chained on `z.date()`, in milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DateChecks {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// Parses `YYYY-MM-DD` or an ISO 8601 date-time like
+/// `2023-11-08T12:00:00.000+02:00` into milliseconds since the Unix epoch.
+/// Unlike `new Date(...)`, a date-time without an offset is read as UTC
+/// rather than local time.
+pub fn parse_date(value: &str) -> Option<i64> {
+    let (date, time) = match value.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = digits(parts.next()?, 4)?;
+    let month: i64 = digits(parts.next()?, 2)?;
+    let day: i64 = digits(parts.next()?, 2)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let ms = days_from_civil(year, month, day) * MS_PER_DAY;
+    match time {
+        Some(time) => Some(ms + parse_time(time)?),
+        None => Some(ms),
+    }
+}
+
+/// Formats like JavaScript's `Date.prototype.toISOString`.
+pub fn format_date(ms: i64) -> String {
+    let (days, ms) = (ms.div_euclid(MS_PER_DAY), ms.rem_euclid(MS_PER_DAY));
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// Parses `HH:MM[:SS[.sss]][Z|±HH:MM]` into milliseconds after midnight UTC.
+fn parse_time(time: &str) -> Option<i64> {
+    let (time, offset) = if let Some(time) = time.strip_suffix('Z') {
+        (time, 0)
+    } else if let Some(at) = time.rfind(['+', '-']) {
+        let (hours, minutes) = time[at + 1..].split_once(':')?;
+        let offset = (digits(hours, 2)? * 60 + digits(minutes, 2)?) * 60_000;
+        let sign = if time[at..].starts_with('-') { -1 } else { 1 };
+        (&time[..at], sign * offset)
+    } else {
+        (time, 0)
+    };
+
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) if !fraction.is_empty() => (time, fraction),
+        Some(_) => return None,
+        None => (time, "0"),
+    };
+    if !fraction.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let ms: i64 = format!("{fraction:0<3}")[..3].parse().ok()?;
+
+    let mut parts = time.splitn(3, ':');
+    let hours = digits(parts.next()?, 2)?;
+    let minutes = digits(parts.next()?, 2)?;
+    let seconds = match parts.next() {
+        Some(seconds) => digits(seconds, 2)?,
+        None => 0,
+    };
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + ms - offset)
+}
+
+/// Parses exactly `len` ASCII digits.
+fn digits(value: &str, len: usize) -> Option<i64> {
+    if value.len() != len || !value.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
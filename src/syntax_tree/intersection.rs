@@ -0,0 +1,88 @@
+use super::{Value, ZodExpression, ZodField};
+use crate::lexer::{Number, Spanned};
+
+/// Finds a key of `b` that `a` also declares with an incompatible type, e.g.
+/// `z.string()` and `z.number()`. Both sides must resolve to objects.
+pub fn conflicting_field<'a>(
+    a: &'a ZodExpression,
+    b: &'a ZodExpression,
+) -> Option<&'a Spanned<String>> {
+    let (mut left, mut right) = (vec![], vec![]);
+    if !object_fields(a, &mut left) || !object_fields(b, &mut right) {
+        return None;
+    }
+
+    for field in right {
+        for other in left.iter().filter(|other| other.key.node == field.key.node) {
+            if !compatible(&other.value.node, &field.value.node) {
+                return Some(&field.key);
+            }
+            if let Some(key) = conflicting_field(&other.value.node, &field.value.node) {
+                return Some(key);
+            }
+        }
+    }
+
+    None
+}
+
+/// Collects the fields of an object or an intersection of objects, looking
+/// through `.optional()` and friends. Returns `false` for anything else.
+pub fn object_fields<'a>(zod: &'a ZodExpression, fields: &mut Vec<&'a ZodField>) -> bool {
+    match zod {
+        ZodExpression::Object(obj, _) => {
+            fields.extend(obj.iter());
+            true
+        }
+        ZodExpression::Intersection(a, b) => object_fields(a, fields) && object_fields(b, fields),
+        ZodExpression::Optional(inner)
+        | ZodExpression::Nullable(inner)
+        | ZodExpression::Nullish(inner) => object_fields(inner, fields),
+        _ => false,
+    }
+}
+
+fn compatible(a: &ZodExpression, b: &ZodExpression) -> bool {
+    if let (ZodExpression::Literal(a), ZodExpression::Literal(b)) = (unwrap(a), unwrap(b)) {
+        return a == b;
+    }
+
+    match (kind(a), kind(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+/// Coarse type of a schema, or `None` when it may intersect with anything.
+fn kind(zod: &ZodExpression) -> Option<&'static str> {
+    match unwrap(zod) {
+        ZodExpression::String(_) | ZodExpression::Enum(_) => Some("string"),
+        ZodExpression::Number(_) | ZodExpression::NaN => Some("number"),
+        ZodExpression::Boolean => Some("boolean"),
+        ZodExpression::Null => Some("null"),
+        ZodExpression::Undefined | ZodExpression::Void => Some("undefined"),
+        ZodExpression::BigInt => Some("bigint"),
+        ZodExpression::Date(_) => Some("date"),
+        ZodExpression::Symbol => Some("symbol"),
+        ZodExpression::Object(..) | ZodExpression::Record { .. } => Some("object"),
+        ZodExpression::Array(_) | ZodExpression::Tuple { .. } => Some("array"),
+        ZodExpression::Map { .. } => Some("map"),
+        ZodExpression::Set(_) => Some("set"),
+        ZodExpression::Literal(Value::String(_)) => Some("string"),
+        ZodExpression::Literal(Value::Number(Number::BigInt(_))) => Some("bigint"),
+        ZodExpression::Literal(Value::Number(_)) => Some("number"),
+        ZodExpression::Literal(Value::Boolean(_)) => Some("boolean"),
+        ZodExpression::Literal(Value::Null) => Some("null"),
+        _ => None,
+    }
+}
+
+fn unwrap(zod: &ZodExpression) -> &ZodExpression {
+    match zod {
+        ZodExpression::Optional(inner)
+        | ZodExpression::Nullable(inner)
+        | ZodExpression::Nullish(inner)
+        | ZodExpression::Default(inner, _) => unwrap(inner),
+        zod => zod,
+    }
+}
@@ -1,4 +1,23 @@
+mod date;
+mod intersection;
+mod native_enum;
+mod number;
+mod object;
+mod string;
+#[allow(clippy::module_inception)]
 mod syntax_tree;
+mod value;
 
+pub use date::{format_date, parse_date, DateChecks};
+pub(crate) use intersection::object_fields;
+pub use native_enum::NativeEnum;
+pub use number::{Bound, NumberChecks};
+pub use object::UnknownKeys;
+pub use string::{DatetimeOptions, IpVersion, Regex, StringChecks, StringFormat, StringTransform};
+pub use syntax_tree::property_key;
+pub use syntax_tree::SyntaxError;
 pub use syntax_tree::SyntaxTree;
 pub use syntax_tree::ZodExpression;
+pub use syntax_tree::ZodField;
+pub use syntax_tree::ZOD_TYPES;
+pub use value::Value;
@@ -0,0 +1,9 @@
+use super::Value;
+
+/// A TypeScript `enum` declaration, referenced by `z.nativeEnum(Name)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NativeEnum {
+    pub name: String,
+    /// Member names and their string or number values, in declaration order.
+    pub members: Vec<(String, Value)>,
+}
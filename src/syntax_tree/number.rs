@@ -0,0 +1,101 @@
+/// Checks chained on `z.number()`, e.g. `.int().min(1)`.
+///
+/// Only the tightest bound on each side is kept.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberChecks {
+    pub int: bool,
+    pub min: Option<Bound>,
+    pub max: Option<Bound>,
+    pub multiple_of: Option<f64>,
+    pub finite: bool,
+    /// `.safe()`: the value must lie within `Number.MIN_SAFE_INTEGER` and
+    /// `Number.MAX_SAFE_INTEGER`.
+    pub safe: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    pub value: f64,
+    /// Whether `value` itself is allowed, as with `.min()` but not `.gt()`.
+    pub inclusive: bool,
+}
+
+impl Bound {
+    pub fn inclusive(value: f64) -> Self {
+        Bound {
+            value,
+            inclusive: true,
+        }
+    }
+
+    pub fn exclusive(value: f64) -> Self {
+        Bound {
+            value,
+            inclusive: false,
+        }
+    }
+}
+
+impl NumberChecks {
+    pub fn set_min(&mut self, bound: Bound) {
+        let tighter = match self.min {
+            Some(min) => bound.value > min.value || (bound.value == min.value && !bound.inclusive),
+            None => true,
+        };
+        if tighter {
+            self.min = Some(bound);
+        }
+    }
+
+    pub fn set_max(&mut self, bound: Bound) {
+        let tighter = match self.max {
+            Some(max) => bound.value < max.value || (bound.value == max.value && !bound.inclusive),
+            None => true,
+        };
+        if tighter {
+            self.max = Some(bound);
+        }
+    }
+
+    /// Adds the checks of `other`, as in an intersection of both schemas.
+    pub fn merge(&mut self, other: &NumberChecks) {
+        self.int |= other.int;
+        self.finite |= other.finite;
+        self.safe |= other.safe;
+        if let Some(min) = other.min {
+            self.set_min(min);
+        }
+        if let Some(max) = other.max {
+            self.set_max(max);
+        }
+        self.multiple_of = match (self.multiple_of, other.multiple_of) {
+            (Some(a), Some(b)) if (b / a).fract() == 0.0 => Some(b),
+            (Some(a), Some(b)) if (a / b).fract() == 0.0 => Some(a),
+            (Some(a), Some(b)) => Some(a * b),
+            (a, b) => a.or(b),
+        };
+    }
+
+    /// Whether `value` passes every check.
+    pub fn accepts(&self, value: f64) -> bool {
+        const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+        let above_min = self
+            .min
+            .is_none_or(|min| value > min.value || (min.inclusive && value == min.value));
+        let below_max = self
+            .max
+            .is_none_or(|max| value < max.value || (max.inclusive && value == max.value));
+        let multiple = self.multiple_of.is_none_or(|step| {
+            let ratio = value / step;
+            (ratio - ratio.round()).abs() < 1e-9
+        });
+
+        above_min
+            && below_max
+            && multiple
+            && value.is_finite()
+            && (!self.int || value.fract() == 0.0)
+            && (!self.safe || value.abs() <= MAX_SAFE_INTEGER)
+    }
+}
@@ -0,0 +1,82 @@
+use super::{ZodExpression, ZodField};
+use crate::lexer::Spanned;
+
+/// How an object treats keys missing from its shape.
+#[derive(Debug, Default)]
+pub enum UnknownKeys {
+    /// Unknown keys are dropped, zod's default and `.strip()`.
+    #[default]
+    Strip,
+    /// Unknown keys are an error, `.strict()`.
+    Strict,
+    /// Unknown keys are kept as they are, `.passthrough()`.
+    Passthrough,
+    /// Unknown keys must match a schema, `.catchall(schema)`.
+    Catchall(Box<Spanned<ZodExpression>>),
+}
+
+/// Adds the fields of `shape` to `fields` like `.extend()`. A key that is
+/// already present keeps its position but takes the new schema.
+pub fn extend(fields: &mut Vec<ZodField>, shape: Vec<ZodField>) {
+    for field in shape {
+        match fields
+            .iter_mut()
+            .find(|other| other.key.node == field.key.node)
+        {
+            Some(other) => *other = field,
+            None => fields.push(field),
+        }
+    }
+}
+
+/// Marks a field schema `.optional()` unless it already is.
+pub fn partial(value: Spanned<ZodExpression>) -> Spanned<ZodExpression> {
+    match value.node {
+        ZodExpression::Optional(_) | ZodExpression::Nullish(_) => value,
+        _ => {
+            let span = value.span;
+            Spanned::new(ZodExpression::Optional(Box::new(value)), span)
+        }
+    }
+}
+
+/// Removes `.optional()` from a field schema, leaving `.nullish()` nullable.
+pub fn required(value: Spanned<ZodExpression>) -> Spanned<ZodExpression> {
+    match value.node {
+        ZodExpression::Optional(inner) => required(*inner),
+        ZodExpression::Nullish(inner) => Spanned::new(ZodExpression::Nullable(inner), value.span),
+        _ => value,
+    }
+}
+
+/// Makes every object field optional, recursing into nested objects, arrays,
+/// tuples and wrapped schemas like `.deepPartial()`.
+pub fn deep_partial(value: Spanned<ZodExpression>) -> Spanned<ZodExpression> {
+    let deep = |value: Box<Spanned<ZodExpression>>| Box::new(deep_partial(*value));
+
+    let node = match value.node {
+        ZodExpression::Object(fields, unknown_keys) => ZodExpression::Object(
+            Box::new(
+                fields
+                    .into_iter()
+                    .map(|field| ZodField {
+                        value: partial(deep_partial(field.value)),
+                        ..field
+                    })
+                    .collect(),
+            ),
+            unknown_keys,
+        ),
+        ZodExpression::Array(item) => ZodExpression::Array(deep(item)),
+        ZodExpression::Tuple { items, rest } => ZodExpression::Tuple {
+            items: items.into_iter().map(deep_partial).collect(),
+            rest: rest.map(deep),
+        },
+        ZodExpression::Optional(inner) => ZodExpression::Optional(deep(inner)),
+        ZodExpression::Nullable(inner) => ZodExpression::Nullable(deep(inner)),
+        ZodExpression::Nullish(inner) => ZodExpression::Nullish(deep(inner)),
+        node => node,
+    };
+
+    Spanned::new(node, value.span)
+}
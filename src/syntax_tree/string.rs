@@ -0,0 +1,95 @@
+/// Checks and transforms chained on `z.string()`, e.g. `.min(3).trim()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StringChecks {
+    /// Minimum length in UTF-16 code units, as measured by JavaScript.
+    pub min: Option<usize>,
+    /// Maximum length in UTF-16 code units.
+    pub max: Option<usize>,
+    /// Format set by methods like `.email()`; the last one chained wins.
+    pub format: Option<StringFormat>,
+    pub regex: Option<Regex>,
+    pub starts_with: Option<String>,
+    pub ends_with: Option<String>,
+    pub includes: Vec<String>,
+    /// Transforms in the order they were chained.
+    pub transforms: Vec<StringTransform>,
+}
+
+impl StringChecks {
+    /// Adds the checks of `other`, as in an intersection of both schemas.
+    /// Where both set a format, regex or affix that can't be combined, the
+    /// first is kept.
+    pub fn merge(&mut self, other: &StringChecks) {
+        self.min = self.min.max(other.min);
+        self.max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.format = self.format.or(other.format);
+        self.regex = self.regex.take().or_else(|| other.regex.clone());
+        self.starts_with = match (self.starts_with.take(), &other.starts_with) {
+            (Some(a), Some(b)) if b.starts_with(a.as_str()) => Some(b.clone()),
+            (a, b) => a.or_else(|| b.clone()),
+        };
+        self.ends_with = match (self.ends_with.take(), &other.ends_with) {
+            (Some(a), Some(b)) if b.ends_with(a.as_str()) => Some(b.clone()),
+            (a, b) => a.or_else(|| b.clone()),
+        };
+        self.includes.extend(other.includes.iter().cloned());
+        self.transforms.extend(other.transforms.iter().copied());
+    }
+}
+
+/// A `/pattern/flags` literal passed to `.regex()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regex {
+    pub pattern: String,
+    pub flags: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringTransform {
+    Trim,
+    ToLowerCase,
+    ToUpperCase,
+}
+
+/// Built-in format a string must follow, e.g. `.email()` or
+/// `.datetime({ offset: true })`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringFormat {
+    Email,
+    Url,
+    Emoji,
+    Uuid,
+    Nanoid,
+    Cuid,
+    Cuid2,
+    Ulid,
+    Datetime(DatetimeOptions),
+    Date,
+    /// `.time()`, with the number of fractional second digits if fixed.
+    Time(Option<u32>),
+    Duration,
+    Ip(Option<IpVersion>),
+    Cidr(Option<IpVersion>),
+    Base64,
+    Base64Url,
+}
+
+/// Options of `.datetime({ ... })`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DatetimeOptions {
+    /// Whether a `+02:00` style offset is accepted in place of `Z`.
+    pub offset: bool,
+    /// Whether the timezone may be left out.
+    pub local: bool,
+    /// Number of fractional second digits, if fixed.
+    pub precision: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
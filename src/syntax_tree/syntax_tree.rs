@@ -1,137 +1,1075 @@
+use std::borrow::Cow;
 use std::iter::Peekable;
-use std::vec;
 use thiserror::Error;
 
-use crate::lexer::Token;
+use super::intersection::conflicting_field;
+use super::object::{deep_partial, extend, partial, required, UnknownKeys};
+use super::{
+    parse_date, Bound, DateChecks, DatetimeOptions, IpVersion, NativeEnum, NumberChecks, Regex,
+    StringChecks, StringFormat, StringTransform, Value,
+};
+use crate::compiler::string_example;
+use crate::lexer::{LexResult, LexerError, Number, Span, Spanned, Token};
+use crate::regex::{self, RegexError};
+use crate::Error;
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum ZodExpression {
-    Object(Box<Vec<(String, ZodExpression)>>),
-    Array(Box<ZodExpression>),
-    Literal(String),
-    Number,
-    UUID,
-    String,
+    Object(Box<Vec<ZodField>>, UnknownKeys),
+    Array(Box<Spanned<ZodExpression>>),
+    /// `z.literal(...)` of a string, number, bigint, boolean or `null`.
+    Literal(Value),
+    Number(NumberChecks),
+    String(StringChecks),
     Boolean,
-    Email,
     Any,
+    Unknown,
+    Null,
+    Undefined,
+    Void,
+    /// Accepts no value at all.
+    Never,
+    NaN,
+    BigInt,
+    Date(DateChecks),
+    Symbol,
     Enum(Vec<String>),
-    Union(Vec<ZodExpression>),
+    /// `z.nativeEnum(Name)`, resolved to the `enum Name { ... }` declaration.
+    NativeEnum(NativeEnum),
+    Union(Vec<Spanned<ZodExpression>>),
+    /// `z.intersection(a, b)` or `a.and(b)`.
+    Intersection(Box<Spanned<ZodExpression>>, Box<Spanned<ZodExpression>>),
+    /// `z.discriminatedUnion(discriminator, [...])`. Every option is an object
+    /// whose `discriminator` field is a distinct literal.
+    DiscriminatedUnion {
+        discriminator: String,
+        options: Vec<Spanned<ZodExpression>>,
+    },
+    /// `z.tuple([...])`, with the element type of `.rest(...)` if any.
+    Tuple {
+        items: Vec<Spanned<ZodExpression>>,
+        rest: Option<Box<Spanned<ZodExpression>>>,
+    },
+    /// `z.record(key, value)`, or `z.record(value)` with string keys.
+    Record {
+        key: Option<Box<Spanned<ZodExpression>>>,
+        value: Box<Spanned<ZodExpression>>,
+    },
+    Map {
+        key: Box<Spanned<ZodExpression>>,
+        value: Box<Spanned<ZodExpression>>,
+    },
+    Set(Box<Spanned<ZodExpression>>),
+    /// `.optional()`: the value may be left out.
+    Optional(Box<Spanned<ZodExpression>>),
+    /// `.nullable()`: the value may be `null`.
+    Nullable(Box<Spanned<ZodExpression>>),
+    /// `.nullish()`: the value may be left out or be `null`.
+    Nullish(Box<Spanned<ZodExpression>>),
+    /// `.default(value)`: `value` is used when the input is left out.
+    Default(Box<Spanned<ZodExpression>>, Value),
+    /// Placeholder for an expression that failed to parse in recovering mode.
+    Error,
 }
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
+pub struct ZodField {
+    pub key: Spanned<String>,
+    pub value: Spanned<ZodExpression>,
+    /// Text of the `/** ... */` comment written directly above the key.
+    pub doc: Option<String>,
+}
+
+#[derive(Error, Debug, PartialEq)]
 pub enum SyntaxError {
     #[error("Expected token {0:?} found {1:?}")]
-    UnexpectedToken(Token, Token),
+    UnexpectedToken(Token<'static>, Token<'static>, Span),
 
     #[error("Invalid identifier {0:?}")]
-    InvalidIdentifier(String),
+    InvalidIdentifier(String, Span),
 
     #[error("Unexpected end of a file")]
-    UnexpectedEndOfFile,
+    UnexpectedEndOfFile(Span),
 
     #[error("Unexpected token in enum {0:?}")]
-    UnexpectedTokenInEnum(Token),
+    UnexpectedTokenInEnum(Token<'static>, Span),
 
     #[error("Unexpected token in object body {0:?}")]
-    UnexpectedTokenInObjectBody(Token),
+    UnexpectedTokenInObjectBody(Token<'static>, Span),
+
+    #[error("Unexpected token in value {0:?}")]
+    UnexpectedTokenInValue(Token<'static>, Span),
+
+    #[error("Invalid regular expression: {0}")]
+    InvalidRegex(RegexError, Span),
+
+    #[error("Invalid date {0:?}")]
+    InvalidDate(String, Span),
+
+    #[error("Discriminated union option has no literal {0:?} field")]
+    InvalidUnionOption(String, Span),
+
+    #[error("Duplicate discriminator value {0}")]
+    DuplicateDiscriminator(String, Span),
+
+    #[error("Conflicting types for key {0:?} in intersection")]
+    IntersectionConflict(String, Span),
+
+    #[error("Invalid argument to .{0}()")]
+    InvalidObjectMethodArgument(String, Span),
+
+    #[error("Undeclared constant {0:?}")]
+    UndeclaredConstant(String, Span),
+
+    #[error("Undeclared enum {0:?}")]
+    UndeclaredEnum(String, Span),
+
+    #[error("Enum member {0:?} must have an initializer")]
+    MissingEnumInitializer(String, Span),
+
+    #[error("No example string satisfies .{0}() and the checks before it")]
+    UnsatisfiableString(String, Span),
 }
 
-pub struct SyntaxTree {
-    tokens: Peekable<vec::IntoIter<Token>>,
+impl SyntaxError {
+    pub fn span(&self) -> Span {
+        match self {
+            SyntaxError::UnexpectedToken(_, _, span)
+            | SyntaxError::InvalidIdentifier(_, span)
+            | SyntaxError::UnexpectedEndOfFile(span)
+            | SyntaxError::UnexpectedTokenInEnum(_, span)
+            | SyntaxError::UnexpectedTokenInObjectBody(_, span)
+            | SyntaxError::UnexpectedTokenInValue(_, span)
+            | SyntaxError::InvalidRegex(_, span)
+            | SyntaxError::InvalidDate(_, span)
+            | SyntaxError::InvalidUnionOption(_, span)
+            | SyntaxError::DuplicateDiscriminator(_, span)
+            | SyntaxError::IntersectionConflict(_, span)
+            | SyntaxError::InvalidObjectMethodArgument(_, span)
+            | SyntaxError::UndeclaredConstant(_, span)
+            | SyntaxError::UndeclaredEnum(_, span)
+            | SyntaxError::MissingEnumInitializer(_, span)
+            | SyntaxError::UnsatisfiableString(_, span) => *span,
+        }
+    }
+}
+
+/// Names accepted after `z.`, used to suggest fixes for invalid identifiers.
+pub const ZOD_TYPES: &[&str] = &[
+    "object",
+    "array",
+    "literal",
+    "number",
+    "enum",
+    "nativeEnum",
+    "string",
+    "boolean",
+    "any",
+    "union",
+    "discriminatedUnion",
+    "intersection",
+    "tuple",
+    "record",
+    "map",
+    "set",
+    "unknown",
+    "null",
+    "undefined",
+    "void",
+    "never",
+    "nan",
+    "bigint",
+    "date",
+    "symbol",
+    "coerce",
+];
+
+/// Parser over a stream of tokens, usually a `Lexer`. Tokens are pulled
+/// lazily as the parse advances.
+pub struct SyntaxTree<'src, I: Iterator<Item = LexResult<'src>>> {
+    tokens: Peekable<I>,
+    /// Span of the most recently consumed token.
+    last_span: Span,
+    /// Doc comment skipped while peeking, waiting for the token it precedes.
+    leading_doc: Option<Cow<'src, str>>,
+    /// Doc comment that preceded the most recently consumed token.
+    last_doc: Option<Cow<'src, str>>,
+    /// Closing tokens of the brackets opened and not yet closed by consumed
+    /// tokens, innermost last.
+    brackets: Vec<Token<'static>>,
+    /// Whether errors are collected into `errors` instead of aborting the parse.
+    recover: bool,
+    errors: Vec<SyntaxError>,
+    /// `enum` declarations read so far.
+    enums: Vec<NativeEnum>,
+    /// `const NAME = "key"` declarations, used by computed object keys.
+    consts: Vec<(String, Value)>,
+    /// Errors reported by the lexer. Outside recovering mode the first one
+    /// ends the token stream.
+    lexer_errors: Vec<LexerError>,
 }
 
-impl SyntaxTree {
-    pub fn new(tokens: Peekable<vec::IntoIter<Token>>) -> SyntaxTree {
-        SyntaxTree { tokens }
+impl<'src, I: Iterator<Item = LexResult<'src>>> SyntaxTree<'src, I> {
+    pub fn new(tokens: I) -> Self {
+        SyntaxTree {
+            tokens: tokens.peekable(),
+            last_span: Span::default(),
+            leading_doc: None,
+            last_doc: None,
+            brackets: Vec::new(),
+            recover: false,
+            errors: Vec::new(),
+            enums: Vec::new(),
+            consts: Vec::new(),
+            lexer_errors: Vec::new(),
+        }
     }
 
-    pub fn parse(&mut self) -> Option<ZodExpression> {
-        match self.tokens.peek() {
-            Some(Token::Ident(ident)) => {
-                if ident == "z" {
-                    match self.parse_zod() {
-                        Result::Ok(zod) => Some(zod),
-                        Err(_) => None,
+    /// Parses a single schema, preceded by any `enum` declarations it uses,
+    /// failing if anything follows it.
+    pub fn parse(&mut self) -> Result<Spanned<ZodExpression>, Error> {
+        let result = self
+            .parse_declarations()
+            .and_then(|_| self.parse_expression())
+            .and_then(|zod| match self.skip_semicolon().next() {
+                Some(token) => Err(SyntaxError::UnexpectedToken(
+                    Token::Eof,
+                    token.into_owned(),
+                    self.last_span,
+                )),
+                None => Ok(zod),
+            });
+
+        // A lexer error cuts the input short, so it is the real cause of
+        // whatever the parser reported.
+        match self.lexer_errors.pop() {
+            Some(err) => Err(err.into()),
+            None => Ok(result?),
+        }
+    }
+
+    /// Parses a schema without stopping at the first error.
+    ///
+    /// Failed expressions are replaced with `ZodExpression::Error` and parsing
+    /// resumes at the next `,`, `}` or `]`. Returns the partial tree together
+    /// with every error that was encountered.
+    pub fn parse_recovering(&mut self) -> (Spanned<ZodExpression>, Vec<Error>) {
+        self.recover = true;
+
+        if let Err(err) = self.parse_declarations() {
+            self.errors.push(err);
+        }
+        let zod = match self.parse_element() {
+            Ok(zod) => zod,
+            Err(err) => {
+                self.errors.push(err);
+                Spanned::new(ZodExpression::Error, self.last_span)
+            }
+        };
+
+        if let Some(token) = self.skip_semicolon().next() {
+            self.errors.push(SyntaxError::UnexpectedToken(
+                Token::Eof,
+                token.into_owned(),
+                self.last_span,
+            ));
+            while self.next().is_some() {}
+        }
+
+        let mut errors: Vec<Error> = self
+            .lexer_errors
+            .drain(..)
+            .map(Error::from)
+            .chain(self.errors.drain(..).map(Error::from))
+            .collect();
+        errors.sort_by_key(|err| err.span().start);
+
+        (zod, errors)
+    }
+
+    /// Skips the `;` that may end the schema statement.
+    fn skip_semicolon(&mut self) -> &mut Self {
+        if self.peek() == Some(&Token::Semicolon) {
+            self.next();
+        }
+
+        self
+    }
+
+    /// Parses a nested expression, replacing it with `ZodExpression::Error`
+    /// when it fails in recovering mode.
+    fn parse_element(&mut self) -> Result<Spanned<ZodExpression>, SyntaxError> {
+        let start = self.peek_span();
+        let depth = self.brackets.len();
+
+        match self.parse_expression() {
+            Err(err) if self.recover => {
+                // Leave the method call the error was found in, if any, so
+                // its `)` isn't mistaken for the end of the enclosing scope.
+                self.skip_to_depth(depth);
+                self.recover_from(err)?;
+                Ok(Spanned::new(ZodExpression::Error, start.to(self.last_span)))
+            }
+            result => result,
+        }
+    }
+
+    /// Records `err` in recovering mode, otherwise returns it. Unlike
+    /// `recover_from` this skips nothing, for errors found after parsing.
+    fn report(&mut self, err: SyntaxError) -> Result<(), SyntaxError> {
+        if !self.recover {
+            return Err(err);
+        }
+        self.errors.push(err);
+
+        Ok(())
+    }
+
+    /// Records `err` and skips to the end of the current scope in recovering
+    /// mode, otherwise returns it.
+    fn recover_from(&mut self, err: SyntaxError) -> Result<(), SyntaxError> {
+        if !self.recover {
+            return Err(err);
+        }
+
+        // Every enclosing scope reports running out of input; keep one.
+        if self.errors.last() != Some(&err) {
+            self.errors.push(err);
+        }
+        self.parse_to_end_of_scope();
+
+        Ok(())
+    }
+
+    fn parse_expression(&mut self) -> Result<Spanned<ZodExpression>, SyntaxError> {
+        let start = self.peek_span();
+
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident == "z" => {
+                let zod = self.parse_zod()?;
+                let mut zod = Spanned::new(zod, start.to(self.last_span));
+
+                while self.peek() == Some(&Token::Dot) {
+                    zod = self.parse_method(zod)?;
+                }
+
+                Ok(zod)
+            }
+            Some(_) => Err(SyntaxError::UnexpectedToken(
+                Token::Ident("z".into()),
+                self.next().unwrap().into_owned(),
+                self.last_span,
+            )),
+            None => Err(SyntaxError::UnexpectedEndOfFile(start)),
+        }
+    }
+
+    /// Parses one chained method call like `.optional()` and applies it to
+    /// `zod`. Methods that don't affect the generated JSON are skipped.
+    fn parse_method(
+        &mut self,
+        zod: Spanned<ZodExpression>,
+    ) -> Result<Spanned<ZodExpression>, SyntaxError> {
+        let start = zod.span;
+        self.parse_dot()?;
+        let method = match self.next() {
+            Some(Token::Ident(ident)) => ident,
+            Some(token) => {
+                return Err(SyntaxError::UnexpectedToken(
+                    Token::Ident("".into()),
+                    token.into_owned(),
+                    self.last_span,
+                ))
+            }
+            None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        };
+        let method_span = self.last_span;
+        self.parse_left_round()?;
+
+        let zod = match method.as_ref() {
+            "optional" => {
+                self.parse_right_round()?;
+                ZodExpression::Optional(Box::new(zod))
+            }
+            "nullable" => {
+                self.parse_right_round()?;
+                ZodExpression::Nullable(Box::new(zod))
+            }
+            "nullish" => {
+                self.parse_right_round()?;
+                ZodExpression::Nullish(Box::new(zod))
+            }
+            "and" => {
+                let other = self.parse_element()?;
+                self.parse_right_round()?;
+                self.intersect(zod, other)?
+            }
+            "default" => {
+                // Only literal defaults are kept, anything else like
+                // `() => []` or `new Date()` leaves the schema as it is.
+                let depth = self.brackets.len();
+                match self.parse_value() {
+                    Ok(value) if self.peek() == Some(&Token::RRound) => {
+                        self.next();
+                        ZodExpression::Default(Box::new(zod), value)
+                    }
+                    _ => {
+                        self.skip_to_depth(depth);
+                        self.skip_arguments()?;
+                        zod.node
+                    }
+                }
+            }
+            _ => match zod.node {
+                ZodExpression::Number(checks) => {
+                    ZodExpression::Number(self.parse_number_check(checks, &method)?)
+                }
+                ZodExpression::String(checks) => {
+                    let checks = self.parse_string_check(checks, &method)?;
+                    if string_example(&checks).is_none() {
+                        let span = method_span.to(self.last_span);
+                        self.report(SyntaxError::UnsatisfiableString(method.to_string(), span))?;
+                    }
+
+                    ZodExpression::String(checks)
+                }
+                ZodExpression::Date(mut checks) if method == "min" || method == "max" => {
+                    let date = self.parse_date_argument()?;
+                    if method == "min" {
+                        checks.min = Some(date);
+                    } else {
+                        checks.max = Some(date);
+                    }
+                    self.skip_arguments()?;
+
+                    ZodExpression::Date(checks)
+                }
+                ZodExpression::Object(fields, unknown_keys) => {
+                    self.parse_object_method(*fields, unknown_keys, &method)?
+                }
+                ZodExpression::Tuple { items, .. } if method == "rest" => {
+                    let rest = self.parse_element()?;
+                    self.parse_right_round()?;
+
+                    ZodExpression::Tuple {
+                        items,
+                        rest: Some(Box::new(rest)),
                     }
+                }
+                node => {
+                    self.skip_arguments()?;
+                    node
+                }
+            },
+        };
+
+        Ok(Spanned::new(zod, start.to(self.last_span)))
+    }
+
+    /// Applies the number check `method` to `checks`, consuming its arguments.
+    fn parse_number_check(
+        &mut self,
+        mut checks: NumberChecks,
+        method: &str,
+    ) -> Result<NumberChecks, SyntaxError> {
+        match method {
+            "int" => checks.int = true,
+            "finite" => checks.finite = true,
+            "safe" => checks.safe = true,
+            "positive" => checks.set_min(Bound::exclusive(0.0)),
+            "nonnegative" => checks.set_min(Bound::inclusive(0.0)),
+            "negative" => checks.set_max(Bound::exclusive(0.0)),
+            "nonpositive" => checks.set_max(Bound::inclusive(0.0)),
+            "min" | "gte" => checks.set_min(Bound::inclusive(self.parse_number_argument()?)),
+            "gt" => checks.set_min(Bound::exclusive(self.parse_number_argument()?)),
+            "max" | "lte" => checks.set_max(Bound::inclusive(self.parse_number_argument()?)),
+            "lt" => checks.set_max(Bound::exclusive(self.parse_number_argument()?)),
+            "multipleOf" | "step" => checks.multiple_of = Some(self.parse_number_argument()?),
+            _ => {}
+        }
+        // Whatever is left, such as a custom error message.
+        self.skip_arguments()?;
+
+        Ok(checks)
+    }
+
+    /// Applies the composition method `method`, such as `.extend()` or
+    /// `.pick()`, or the unknown key policy such as `.strict()` to an object,
+    /// consuming its arguments.
+    fn parse_object_method(
+        &mut self,
+        mut fields: Vec<ZodField>,
+        mut unknown_keys: UnknownKeys,
+        method: &str,
+    ) -> Result<ZodExpression, SyntaxError> {
+        match method {
+            "extend" => {
+                let shape = self.parse_object_shape()?;
+                extend(&mut fields, shape);
+            }
+            "merge" => {
+                let other = self.parse_element()?;
+                match other.node {
+                    // Like zod, the merged object's policy wins.
+                    ZodExpression::Object(shape, policy) => {
+                        extend(&mut fields, *shape);
+                        unknown_keys = policy;
+                    }
+                    ZodExpression::Error => {}
+                    _ => self.report(SyntaxError::InvalidObjectMethodArgument(
+                        method.to_string(),
+                        other.span,
+                    ))?,
+                }
+            }
+            "pick" | "omit" => {
+                let keys = self.parse_mask(method)?;
+                fields.retain(|field| keys.contains(&field.key.node) == (method == "pick"));
+            }
+            "partial" | "required" => {
+                let keys = match self.peek() {
+                    Some(Token::LCurly) => Some(self.parse_mask(method)?),
+                    _ => None,
+                };
+                let apply = if method == "partial" {
+                    partial
                 } else {
-                    None
+                    required
+                };
+
+                fields = fields
+                    .into_iter()
+                    .map(|field| match &keys {
+                        Some(keys) if !keys.contains(&field.key.node) => field,
+                        _ => ZodField {
+                            value: apply(field.value),
+                            ..field
+                        },
+                    })
+                    .collect();
+            }
+            "strip" => unknown_keys = UnknownKeys::Strip,
+            "strict" => unknown_keys = UnknownKeys::Strict,
+            "passthrough" => unknown_keys = UnknownKeys::Passthrough,
+            "catchall" => {
+                let schema = self.parse_element()?;
+                unknown_keys = UnknownKeys::Catchall(Box::new(schema));
+            }
+            "deepPartial" => {
+                fields = fields
+                    .into_iter()
+                    .map(|field| ZodField {
+                        value: partial(deep_partial(field.value)),
+                        ..field
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+        self.skip_arguments()?;
+
+        Ok(ZodExpression::Object(Box::new(fields), unknown_keys))
+    }
+
+    /// Parses a mask like `{ id: true, name: true }` into its keys.
+    fn parse_mask(&mut self, method: &str) -> Result<Vec<String>, SyntaxError> {
+        let start = self.peek_span();
+        let mask = self.parse_value()?;
+
+        match mask {
+            Value::Object(entries)
+                if entries
+                    .iter()
+                    .all(|(_, value)| *value == Value::Boolean(true)) =>
+            {
+                Ok(entries.into_iter().map(|(key, _)| key).collect())
+            }
+            _ => Err(SyntaxError::InvalidObjectMethodArgument(
+                method.to_string(),
+                start.to(self.last_span),
+            )),
+        }
+    }
+
+    /// Applies the string check or transform `method` to `checks`, consuming
+    /// its arguments.
+    fn parse_string_check(
+        &mut self,
+        mut checks: StringChecks,
+        method: &str,
+    ) -> Result<StringChecks, SyntaxError> {
+        match method {
+            "min" => checks.min = Some(self.parse_length_argument()?),
+            "max" => checks.max = Some(self.parse_length_argument()?),
+            "length" => {
+                let length = self.parse_length_argument()?;
+                checks.min = Some(length);
+                checks.max = Some(length);
+            }
+            "nonempty" => checks.min = Some(checks.min.unwrap_or(0).max(1)),
+            "regex" => match self.next() {
+                Some(Token::Regex(literal)) => {
+                    // The lexer guarantees the literal is `/pattern/flags`.
+                    let (pattern, flags) = literal[1..].rsplit_once('/').unwrap_or_default();
+                    match regex::parse(pattern).map(|pattern| pattern.example()) {
+                        Ok(Some(_)) => {}
+                        Ok(None) => {
+                            return Err(SyntaxError::InvalidRegex(
+                                RegexError::Unsupported,
+                                self.last_span,
+                            ))
+                        }
+                        Err(err) => return Err(SyntaxError::InvalidRegex(err, self.last_span)),
+                    }
+                    checks.regex = Some(Regex {
+                        pattern: pattern.to_string(),
+                        flags: flags.to_string(),
+                    })
+                }
+                Some(token) => {
+                    return Err(SyntaxError::UnexpectedTokenInValue(
+                        token.into_owned(),
+                        self.last_span,
+                    ))
                 }
+                None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+            },
+            "startsWith" => checks.starts_with = Some(self.parse_string_argument()?),
+            "endsWith" => checks.ends_with = Some(self.parse_string_argument()?),
+            "includes" => checks.includes.push(self.parse_string_argument()?),
+            "email" => checks.format = Some(StringFormat::Email),
+            "url" => checks.format = Some(StringFormat::Url),
+            "emoji" => checks.format = Some(StringFormat::Emoji),
+            "uuid" => checks.format = Some(StringFormat::Uuid),
+            "nanoid" => checks.format = Some(StringFormat::Nanoid),
+            "cuid" => checks.format = Some(StringFormat::Cuid),
+            "cuid2" => checks.format = Some(StringFormat::Cuid2),
+            "ulid" => checks.format = Some(StringFormat::Ulid),
+            "date" => checks.format = Some(StringFormat::Date),
+            "duration" => checks.format = Some(StringFormat::Duration),
+            "base64" => checks.format = Some(StringFormat::Base64),
+            "base64url" => checks.format = Some(StringFormat::Base64Url),
+            "datetime" => {
+                let options = self.parse_options()?;
+                checks.format = Some(StringFormat::Datetime(DatetimeOptions {
+                    offset: options.get("offset") == Some(&Value::Boolean(true)),
+                    local: options.get("local") == Some(&Value::Boolean(true)),
+                    precision: precision_option(&options),
+                }));
+            }
+            "time" => {
+                let options = self.parse_options()?;
+                checks.format = Some(StringFormat::Time(precision_option(&options)));
+            }
+            "ip" => {
+                let options = self.parse_options()?;
+                checks.format = Some(StringFormat::Ip(version_option(&options)));
+            }
+            "cidr" => {
+                let options = self.parse_options()?;
+                checks.format = Some(StringFormat::Cidr(version_option(&options)));
+            }
+            "trim" => checks.transforms.push(StringTransform::Trim),
+            "toLowerCase" => checks.transforms.push(StringTransform::ToLowerCase),
+            "toUpperCase" => checks.transforms.push(StringTransform::ToUpperCase),
+            _ => {}
+        }
+        self.skip_arguments()?;
+
+        Ok(checks)
+    }
+
+    /// Parses the `{ ... }` options argument of a method if there is one.
+    /// A plain error message argument is left for `skip_arguments`.
+    fn parse_options(&mut self) -> Result<Value, SyntaxError> {
+        match self.peek() {
+            Some(Token::LCurly) => self.parse_value(),
+            _ => Ok(Value::Object(vec![])),
+        }
+    }
+
+    fn parse_length_argument(&mut self) -> Result<usize, SyntaxError> {
+        match self.next() {
+            Some(Token::Number(Number::Integer(value))) if value >= 0 => Ok(value as usize),
+            // Integral floats like `1e3` are lengths too.
+            Some(Token::Number(Number::Float(value))) if value >= 0.0 && value.fract() == 0.0 => {
+                Ok(value as usize)
+            }
+            Some(token) => Err(SyntaxError::UnexpectedTokenInValue(
+                token.into_owned(),
+                self.last_span,
+            )),
+            None => Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        }
+    }
+
+    fn parse_string_argument(&mut self) -> Result<String, SyntaxError> {
+        match self.next() {
+            Some(Token::Str(value)) => Ok(value.into_owned()),
+            Some(token) => Err(SyntaxError::UnexpectedTokenInValue(
+                token.into_owned(),
+                self.last_span,
+            )),
+            None => Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        }
+    }
+
+    /// Parses a `new Date(...)` argument into milliseconds since the epoch.
+    fn parse_date_argument(&mut self) -> Result<i64, SyntaxError> {
+        for expected in ["new", "Date"] {
+            match self.next() {
+                Some(Token::Ident(ident)) if ident == expected => {}
+                Some(token) => {
+                    return Err(SyntaxError::UnexpectedToken(
+                        Token::Ident(expected.into()),
+                        token.into_owned(),
+                        self.last_span,
+                    ))
+                }
+                None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+            }
+        }
+        self.parse_left_round()?;
+
+        let date = match self.next() {
+            Some(Token::Number(Number::Integer(ms))) => ms,
+            Some(Token::Str(value)) => match parse_date(&value) {
+                Some(ms) => ms,
+                None => return Err(SyntaxError::InvalidDate(value.into_owned(), self.last_span)),
+            },
+            Some(token) => {
+                return Err(SyntaxError::UnexpectedTokenInValue(
+                    token.into_owned(),
+                    self.last_span,
+                ))
+            }
+            None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        };
+        self.parse_right_round()?;
+
+        Ok(date)
+    }
+
+    fn parse_number_argument(&mut self) -> Result<f64, SyntaxError> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(value.as_f64()),
+            Some(token) => Err(SyntaxError::UnexpectedTokenInValue(
+                token.into_owned(),
+                self.last_span,
+            )),
+            None => Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        }
+    }
+
+    /// Parses a literal value: a string, number, boolean, `null`, or an array
+    /// or object of those.
+    fn parse_value(&mut self) -> Result<Value, SyntaxError> {
+        match self.next() {
+            Some(Token::LSquare) => {
+                let mut items = vec![];
+
+                while self.peek() != Some(&Token::RSquare) {
+                    items.push(self.parse_value()?);
+
+                    if self.peek() != Some(&Token::RSquare) {
+                        self.expect(Token::Comma)?;
+                    }
+                }
+                self.parse_right_square()?;
+
+                Ok(Value::Array(items))
+            }
+            Some(Token::LCurly) => {
+                let mut entries = vec![];
+
+                while self.peek() != Some(&Token::RCurly) {
+                    let key = match self.next() {
+                        Some(Token::Ident(key) | Token::Str(key)) => key.into_owned(),
+                        Some(Token::Number(key @ (Number::Integer(_) | Number::Float(_)))) => {
+                            key.to_string()
+                        }
+                        Some(token) => {
+                            return Err(SyntaxError::UnexpectedTokenInObjectBody(
+                                token.into_owned(),
+                                self.last_span,
+                            ))
+                        }
+                        None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+                    };
+                    self.parse_colon()?;
+                    entries.push((key, self.parse_value()?));
+
+                    if self.peek() != Some(&Token::RCurly) {
+                        self.expect(Token::Comma)?;
+                    }
+                }
+                self.expect(Token::RCurly)?;
+
+                Ok(Value::Object(entries))
+            }
+            Some(token) => scalar_value(token).map_err(|token| {
+                SyntaxError::UnexpectedTokenInValue(token.into_owned(), self.last_span)
+            }),
+            None => Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        }
+    }
+
+    /// Skips tokens until the brackets opened since `depth` are closed. Stops
+    /// early at a closer of an enclosing bracket, which a bracket left open
+    /// by broken input would otherwise swallow.
+    fn skip_to_depth(&mut self, depth: usize) {
+        while self.brackets.len() > depth {
+            let Some(token) = self.peek().cloned() else {
+                break;
+            };
+            if self.brackets.last() != Some(&token) && self.brackets[..depth].contains(&token) {
+                break;
+            }
+            self.next();
+        }
+    }
+
+    /// Skips the arguments of a method call up to and including its `)`.
+    fn skip_arguments(&mut self) -> Result<(), SyntaxError> {
+        let mut depth = 0usize;
+
+        loop {
+            match self.next() {
+                Some(Token::RRound) if depth == 0 => return Ok(()),
+                Some(token @ (Token::RSquare | Token::RCurly)) if depth == 0 => {
+                    return Err(SyntaxError::UnexpectedToken(
+                        Token::RRound,
+                        token.into_owned(),
+                        self.last_span,
+                    ))
+                }
+                Some(Token::LRound | Token::LSquare | Token::LCurly) => depth += 1,
+                Some(Token::RRound | Token::RSquare | Token::RCurly) => depth -= 1,
+                Some(_) => {}
+                None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
             }
-            _ => None,
         }
     }
 
     fn parse_zod(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_dot()?;
-        let ident = match self.tokens.peek() {
+        let ident = match self.peek() {
             Some(Token::Ident(ident)) => ident,
             Some(_) => {
                 return Err(SyntaxError::UnexpectedToken(
-                    self.tokens.next().unwrap(),
-                    Token::Ident("".to_string()),
+                    Token::Ident("".into()),
+                    self.next().unwrap().into_owned(),
+                    self.last_span,
                 ))
             }
-            None => return Err(SyntaxError::UnexpectedEndOfFile),
+            None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
         };
 
-        match ident.as_str() {
+        match ident.as_ref() {
             "object" => self.parse_zod_object_body(),
             "array" => self.parse_zod_array(),
             "literal" => self.parse_zod_literal(),
             "number" => self.parse_zod_number(),
             "enum" => self.parse_zod_enum(),
+            "nativeEnum" => self.parse_zod_native_enum(),
             "string" => self.parse_zod_string(),
             "boolean" => self.parse_zod_boolean(),
             "any" => self.parse_zod_any(),
             "union" => self.parse_zod_union(),
+            "discriminatedUnion" => self.parse_zod_discriminated_union(),
+            "intersection" => self.parse_zod_intersection(),
+            "tuple" => self.parse_zod_tuple(),
+            "unknown" => self.parse_zod_primitive(ZodExpression::Unknown),
+            "null" => self.parse_zod_primitive(ZodExpression::Null),
+            "undefined" => self.parse_zod_primitive(ZodExpression::Undefined),
+            "void" => self.parse_zod_primitive(ZodExpression::Void),
+            "never" => self.parse_zod_primitive(ZodExpression::Never),
+            "nan" => self.parse_zod_primitive(ZodExpression::NaN),
+            "bigint" => self.parse_zod_primitive(ZodExpression::BigInt),
+            "date" => self.parse_zod_primitive(ZodExpression::Date(DateChecks::default())),
+            "symbol" => self.parse_zod_primitive(ZodExpression::Symbol),
+            "record" => self.parse_zod_record(),
+            "map" => self.parse_zod_map(),
+            "set" => self.parse_zod_set(),
             "coerce" => self.parse_zod(),
-            _ => Err(SyntaxError::InvalidIdentifier(ident.to_string())),
+            _ => Err(SyntaxError::InvalidIdentifier(
+                ident.to_string(),
+                self.peek_span(),
+            )),
         }
     }
 
     fn parse_zod_literal(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_left_round()?;
-        match self.next() {
-            Some(Token::Str(value)) => {
-                self.parse_right_round()?;
-                Ok(ZodExpression::Literal(value))
-            }
-            Some(token) => Err(SyntaxError::UnexpectedToken(
-                token,
-                Token::Str("\"\"".to_string()),
-            )),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
-        }
+        let value = match self.next() {
+            Some(token) => scalar_value(token).map_err(|token| {
+                SyntaxError::UnexpectedTokenInValue(token.into_owned(), self.last_span)
+            })?,
+            None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        };
+        self.parse_right_round()?;
+
+        Ok(ZodExpression::Literal(value))
     }
 
     fn parse_zod_union(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_left_round()?;
+        let arr = self.parse_expression_list()?;
+        self.parse_right_round()?;
+
+        Ok(ZodExpression::Union(arr))
+    }
+
+    fn parse_zod_discriminated_union(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let discriminator = self.parse_string_argument()?;
+        self.expect(Token::Comma)?;
+        let options = self.parse_expression_list()?;
+        self.parse_right_round()?;
+
+        let mut values = vec![];
+        for option in &options {
+            let value = match &option.node {
+                ZodExpression::Error => continue,
+                ZodExpression::Object(fields, _) => fields
+                    .iter()
+                    .find(|field| field.key.node == discriminator)
+                    .and_then(|field| match &field.value.node {
+                        ZodExpression::Literal(value) => Some((value, field.value.span)),
+                        _ => None,
+                    }),
+                _ => None,
+            };
+
+            let err = match value {
+                None => SyntaxError::InvalidUnionOption(discriminator.clone(), option.span),
+                Some((value, span)) if values.contains(&value) => {
+                    SyntaxError::DuplicateDiscriminator(property_key(value), span)
+                }
+                Some((value, _)) => {
+                    values.push(value);
+                    continue;
+                }
+            };
+            self.report(err)?;
+        }
+
+        Ok(ZodExpression::DiscriminatedUnion {
+            discriminator,
+            options,
+        })
+    }
+
+    fn parse_zod_intersection(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let a = self.parse_element()?;
+        self.expect(Token::Comma)?;
+        let b = self.parse_element()?;
+        self.parse_right_round()?;
+
+        self.intersect(a, b)
+    }
+
+    /// Builds `a & b`, reporting a key declared by both with conflicting types.
+    fn intersect(
+        &mut self,
+        a: Spanned<ZodExpression>,
+        b: Spanned<ZodExpression>,
+    ) -> Result<ZodExpression, SyntaxError> {
+        if let Some(key) = conflicting_field(&a.node, &b.node) {
+            let err = SyntaxError::IntersectionConflict(key.node.clone(), key.span);
+            self.report(err)?;
+        }
+
+        Ok(ZodExpression::Intersection(Box::new(a), Box::new(b)))
+    }
+
+    fn parse_zod_tuple(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let items = self.parse_expression_list()?;
+        self.parse_right_round()?;
+
+        Ok(ZodExpression::Tuple { items, rest: None })
+    }
+
+    fn parse_zod_record(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let first = self.parse_element()?;
+
+        let zod = match self.peek() {
+            Some(Token::Comma) => {
+                self.next();
+                ZodExpression::Record {
+                    key: Some(Box::new(first)),
+                    value: Box::new(self.parse_element()?),
+                }
+            }
+            _ => ZodExpression::Record {
+                key: None,
+                value: Box::new(first),
+            },
+        };
+        self.parse_right_round()?;
+
+        Ok(zod)
+    }
+
+    fn parse_zod_map(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let key = self.parse_element()?;
+        self.expect(Token::Comma)?;
+        let value = self.parse_element()?;
+        self.parse_right_round()?;
+
+        Ok(ZodExpression::Map {
+            key: Box::new(key),
+            value: Box::new(value),
+        })
+    }
+
+    fn parse_zod_set(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let exp = self.parse_element()?;
+        self.parse_right_round()?;
+
+        Ok(ZodExpression::Set(Box::new(exp)))
+    }
+
+    /// Parses a `[...]` list of schemas.
+    fn parse_expression_list(&mut self) -> Result<Vec<Spanned<ZodExpression>>, SyntaxError> {
         self.parse_left_square()?;
 
         let mut arr = vec![];
 
-        loop {
-            match self.parse() {
-                Some(e) => arr.push(e),
-                None => break,
-            };
+        while self.peek() != Some(&Token::RSquare) {
+            arr.push(self.parse_element()?);
 
-            match self.next() {
+            match self.peek() {
                 Some(Token::RSquare) => break,
-                Some(Token::Comma) => continue,
-                Some(token) => return Err(SyntaxError::UnexpectedToken(token, Token::RSquare)),
-                None => return Err(SyntaxError::UnexpectedEndOfFile),
+                Some(Token::Comma) => self.next(),
+                Some(_) => {
+                    return Err(SyntaxError::UnexpectedToken(
+                        Token::RSquare,
+                        self.next().unwrap().into_owned(),
+                        self.last_span,
+                    ))
+                }
+                None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
             };
         }
-        self.parse_right_round()?;
-        self.parse_to_end_of_scope();
+        self.parse_right_square()?;
 
-        Ok(ZodExpression::Union(arr))
+        Ok(arr)
     }
 
     fn parse_zod_enum(&mut self) -> Result<ZodExpression, SyntaxError> {
@@ -150,23 +1088,184 @@ impl SyntaxTree {
                 Some(Token::Comma) => {
                     continue;
                 }
-                Some(Token::Str(ref str)) => {
-                    arr.push(str.to_owned());
+                Some(Token::Str(value)) => {
+                    arr.push(value.into_owned());
                 }
                 None => {
-                    return Err(SyntaxError::UnexpectedEndOfFile);
+                    return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span()));
                 }
                 Some(token) => {
-                    return Err(SyntaxError::UnexpectedTokenInEnum(token));
+                    self.recover_from(SyntaxError::UnexpectedTokenInEnum(
+                        token.into_owned(),
+                        self.last_span,
+                    ))?;
                 }
             }
         }
         self.parse_right_round()?;
-        self.parse_to_end_of_scope();
 
         Ok(ZodExpression::Enum(arr))
     }
 
+    /// Parses a type that takes no arguments, like `z.null()`.
+    fn parse_zod_primitive(&mut self, zod: ZodExpression) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        self.parse_right_round()?;
+
+        Ok(zod)
+    }
+
+    fn parse_zod_native_enum(&mut self) -> Result<ZodExpression, SyntaxError> {
+        self.next();
+        self.parse_left_round()?;
+        let name = self.parse_name()?;
+        let span = self.last_span;
+        self.parse_right_round()?;
+
+        match self
+            .enums
+            .iter()
+            .rev()
+            .find(|declared| declared.name == name)
+        {
+            Some(declared) => Ok(ZodExpression::NativeEnum(declared.clone())),
+            None => Err(SyntaxError::UndeclaredEnum(name, span)),
+        }
+    }
+
+    /// Parses top level `enum` declarations, optionally `export`ed, `declare`d
+    /// or `const`.
+    fn parse_declarations(&mut self) -> Result<(), SyntaxError> {
+        loop {
+            match self.peek() {
+                Some(Token::Ident(ident)) if matches!(ident.as_ref(), "export" | "declare") => {
+                    self.next();
+                }
+                Some(Token::Ident(ident)) if ident == "const" => {
+                    self.next();
+                    if !matches!(self.peek(), Some(Token::Ident(ident)) if ident == "enum") {
+                        self.parse_const_declaration()?;
+                    }
+                }
+                Some(Token::Ident(ident)) if ident == "enum" => self.parse_enum_declaration()?,
+                Some(Token::Semicolon) => {
+                    self.next();
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Parses `enum Name { A = "a", B = 1, C }`. Members without an
+    /// initializer continue counting from the previous numeric member.
+    fn parse_enum_declaration(&mut self) -> Result<(), SyntaxError> {
+        self.next();
+        let name = self.parse_name()?;
+        self.parse_left_curly()?;
+
+        let mut members = vec![];
+        let mut next = Some(Number::Integer(0));
+
+        loop {
+            match self.next() {
+                Some(Token::RCurly) => break,
+                Some(Token::Comma) => continue,
+                Some(Token::Ident(member) | Token::Str(member)) => {
+                    let member = member.into_owned();
+                    let span = self.last_span;
+
+                    let value = if self.peek() == Some(&Token::Equals) {
+                        self.next();
+                        match self.next() {
+                            Some(Token::Str(value)) => Value::String(value.into_owned()),
+                            Some(Token::Number(
+                                value @ (Number::Integer(_) | Number::Float(_)),
+                            )) => Value::Number(value),
+                            Some(token) => {
+                                self.recover_from(SyntaxError::UnexpectedTokenInValue(
+                                    token.into_owned(),
+                                    self.last_span,
+                                ))?;
+                                continue;
+                            }
+                            None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+                        }
+                    } else {
+                        match next {
+                            Some(value) => Value::Number(value),
+                            None => {
+                                self.recover_from(SyntaxError::MissingEnumInitializer(
+                                    member, span,
+                                ))?;
+                                continue;
+                            }
+                        }
+                    };
+
+                    next = match value {
+                        Value::Number(Number::Integer(value)) => Some(Number::Integer(value + 1)),
+                        Value::Number(Number::Float(value)) => Some(Number::Float(value + 1.0)),
+                        _ => None,
+                    };
+                    members.push((member, value));
+                }
+                Some(token) => {
+                    self.recover_from(SyntaxError::UnexpectedTokenInEnum(
+                        token.into_owned(),
+                        self.last_span,
+                    ))?;
+                }
+                None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+            }
+        }
+
+        self.enums.push(NativeEnum { name, members });
+
+        Ok(())
+    }
+
+    /// Parses `NAME = "key"` or `NAME = 1` after `const`, with an optional
+    /// `as const`.
+    fn parse_const_declaration(&mut self) -> Result<(), SyntaxError> {
+        let name = self.parse_name()?;
+        self.expect(Token::Equals)?;
+        let value = match self.next() {
+            Some(Token::Str(value)) => Value::String(value.into_owned()),
+            Some(Token::Number(value @ (Number::Integer(_) | Number::Float(_)))) => {
+                Value::Number(value)
+            }
+            Some(token) => {
+                return Err(SyntaxError::UnexpectedTokenInValue(
+                    token.into_owned(),
+                    self.last_span,
+                ))
+            }
+            None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        };
+
+        if matches!(self.peek(), Some(Token::Ident(ident)) if ident == "as") {
+            self.next();
+            self.expect(Token::Ident("const".into()))?;
+        }
+        self.consts.push((name, value));
+
+        Ok(())
+    }
+
+    /// Parses an identifier naming a declaration.
+    fn parse_name(&mut self) -> Result<String, SyntaxError> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(name.into_owned()),
+            Some(token) => Err(SyntaxError::UnexpectedToken(
+                Token::Ident("".into()),
+                token.into_owned(),
+                self.last_span,
+            )),
+            None => Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+        }
+    }
+
     fn parse_zod_any(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_left_round()?;
@@ -186,20 +1285,18 @@ impl SyntaxTree {
     fn parse_zod_array(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_left_round()?;
-        let exp = match self.parse() {
-            Some(e) => e,
-            None => return Err(SyntaxError::UnexpectedEndOfFile),
-        };
-        self.parse_to_end_of_scope();
+        let exp = self.parse_element()?;
+        self.parse_right_round()?;
 
         Ok(ZodExpression::Array(Box::new(exp)))
     }
+
     fn parse_zod_number(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_left_round()?;
         self.parse_right_round()?;
-        self.parse_to_end_of_scope();
-        Ok(ZodExpression::Number)
+
+        Ok(ZodExpression::Number(NumberChecks::default()))
     }
 
     fn parse_zod_string(&mut self) -> Result<ZodExpression, SyntaxError> {
@@ -207,137 +1304,297 @@ impl SyntaxTree {
         self.parse_left_round()?;
         self.parse_right_round()?;
 
-        loop {
-            if self.tokens.peek() != Some(&Token::Dot) {
-                break;
-            }
-
-            self.next();
-            let iden = match self.tokens.peek() {
-                Some(Token::Ident(ident)) => ident,
-                Some(_) => {
-                    return Err(SyntaxError::UnexpectedToken(
-                        self.next().unwrap(),
-                        Token::Ident("".to_string()),
-                    ))
-                }
-                None => return Err(SyntaxError::UnexpectedEndOfFile),
-            };
-            if iden == "email" {
-                self.parse_to_end_of_scope();
-                return Ok(ZodExpression::Email);
-            }
-            if iden == "uuid" {
-                self.parse_to_end_of_scope();
-                return Ok(ZodExpression::UUID);
-            }
-        }
-        self.parse_to_end_of_scope();
-        Ok(ZodExpression::String)
+        Ok(ZodExpression::String(StringChecks::default()))
     }
 
+    /// Skips the rest of a broken expression, leaving the closing bracket of
+    /// the enclosing scope unconsumed.
     fn parse_to_end_of_scope(&mut self) {
-        while let Some(token) = self.tokens.peek() {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek() {
             match token {
-                Token::Eof | Token::RSquare | Token::Comma | Token::RCurly => {
-                    break;
-                }
-                _ => {
-                    self.next();
-                    continue;
-                }
+                Token::Eof => break,
+                Token::Comma if depth == 0 => break,
+                Token::RSquare | Token::RCurly | Token::RRound if depth == 0 => break,
+                Token::LRound | Token::LSquare | Token::LCurly => depth += 1,
+                Token::RRound | Token::RSquare | Token::RCurly => depth -= 1,
+                _ => {}
             }
+
+            self.next();
         }
     }
 
     fn parse_zod_object_body(&mut self) -> Result<ZodExpression, SyntaxError> {
         self.next();
         self.parse_left_round()?;
+        let obj = self.parse_object_shape()?;
+        self.parse_right_round()?;
+
+        Ok(ZodExpression::Object(Box::new(obj), UnknownKeys::default()))
+    }
+
+    /// Parses the `{ key: schema, ... }` shape of an object.
+    fn parse_object_shape(&mut self) -> Result<Vec<ZodField>, SyntaxError> {
         self.parse_left_curly()?;
         let mut obj = vec![];
 
         loop {
-            let token = self.next();
-            match token {
-                Some(Token::RCurly) => {
-                    break;
-                }
-                Some(Token::RRound) => {
-                    continue;
-                }
+            let token = match self.next() {
+                Some(Token::RCurly) => break,
+                Some(token) => token,
+                None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+            };
+            obj.extend(self.parse_object_field(token)?);
+
+            match self.peek() {
                 Some(Token::Comma) => {
-                    continue;
-                }
-                Some(Token::Ident(ref ident)) => {
-                    self.parse_colon()?;
-                    let exp = match self.parse() {
-                        Some(e) => e,
-                        None => break,
-                    };
-                    obj.push((ident.to_owned(), exp));
+                    self.next();
                 }
-                Some(token) => {
-                    return Err(SyntaxError::UnexpectedTokenInObjectBody(token));
+                Some(Token::RCurly) | None => {}
+                // A missing comma before the next key is reported, and the
+                // key is parsed as if the comma was there.
+                Some(
+                    token @ (Token::Ident(_) | Token::Str(_) | Token::Number(_) | Token::LSquare),
+                ) => {
+                    let token = token.clone().into_owned();
+                    let span = self.peek_span();
+                    self.report(SyntaxError::UnexpectedToken(Token::Comma, token, span))?;
                 }
-                None => {
-                    return Err(SyntaxError::UnexpectedEndOfFile);
+                Some(_) => {
+                    let token = self.next().unwrap().into_owned();
+                    self.recover_from(SyntaxError::UnexpectedToken(
+                        Token::Comma,
+                        token,
+                        self.last_span,
+                    ))?;
+                    if self.peek() == Some(&Token::Comma) {
+                        self.next();
+                    }
                 }
             }
         }
-        self.parse_right_round()?;
 
-        Ok(ZodExpression::Object(Box::new(obj)))
+        Ok(obj)
     }
 
-    fn parse_left_round(&mut self) -> Result<(), SyntaxError> {
-        match self.next() {
-            Some(Token::LRound) => Ok(()),
-            Some(token) => Err(SyntaxError::UnexpectedToken(token, Token::LRound)),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
+    /// Parses a `key: schema` property starting with `token`. Returns `None`
+    /// when it was skipped after an error in recovering mode.
+    fn parse_object_field(&mut self, token: Token<'src>) -> Result<Option<ZodField>, SyntaxError> {
+        let doc = self.last_doc.take().map(Cow::into_owned);
+        let key = match self.parse_object_key(token) {
+            Ok(key) => key,
+            Err(err) => {
+                self.recover_from(err)?;
+                return Ok(None);
+            }
+        };
+        if let Err(err) = self.parse_colon() {
+            self.recover_from(err)?;
+            return Ok(None);
         }
+        let value = self.parse_element()?;
+
+        Ok(Some(ZodField { key, value, doc }))
+    }
+
+    /// Parses an object key starting with `token`: an identifier, a string or
+    /// number literal, or a computed `[NAME]` naming a `const` declaration.
+    fn parse_object_key(&mut self, token: Token<'src>) -> Result<Spanned<String>, SyntaxError> {
+        let start = self.last_span;
+        let key = match token {
+            Token::Ident(key) | Token::Str(key) => key.into_owned(),
+            Token::Number(key @ (Number::Integer(_) | Number::Float(_))) => key.to_string(),
+            Token::LSquare => {
+                let key = match self.next() {
+                    Some(Token::Str(key)) => Ok(key.into_owned()),
+                    Some(Token::Ident(name)) => self
+                        .consts
+                        .iter()
+                        .rev()
+                        .find(|(declared, _)| *declared == name)
+                        .map(|(_, value)| property_key(value))
+                        .ok_or_else(|| {
+                            SyntaxError::UndeclaredConstant(name.into_owned(), self.last_span)
+                        }),
+                    Some(token) => {
+                        return Err(SyntaxError::UnexpectedTokenInObjectBody(
+                            token.into_owned(),
+                            self.last_span,
+                        ))
+                    }
+                    None => return Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
+                };
+                // Consume the `]` first so recovery resumes after the key.
+                self.parse_right_square()?;
+                key?
+            }
+            token => {
+                return Err(SyntaxError::UnexpectedTokenInObjectBody(
+                    token.into_owned(),
+                    start,
+                ))
+            }
+        };
+
+        Ok(Spanned::new(key, start.to(self.last_span)))
+    }
+
+    fn parse_left_round(&mut self) -> Result<(), SyntaxError> {
+        self.expect(Token::LRound)
     }
 
     fn parse_right_round(&mut self) -> Result<(), SyntaxError> {
-        match self.next() {
-            Some(Token::RRound) => Ok(()),
-            Some(token) => Err(SyntaxError::UnexpectedToken(token, Token::RRound)),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
-        }
+        self.expect(Token::RRound)
     }
 
     fn parse_left_curly(&mut self) -> Result<(), SyntaxError> {
-        match self.next() {
-            Some(Token::LCurly) => Ok(()),
-            Some(token) => Err(SyntaxError::UnexpectedToken(token, Token::LCurly)),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
-        }
+        self.expect(Token::LCurly)
     }
 
     fn parse_colon(&mut self) -> Result<(), SyntaxError> {
-        match self.next() {
-            Some(Token::Colon) => Ok(()),
-            Some(token) => Err(SyntaxError::UnexpectedToken(token, Token::Colon)),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
-        }
+        self.expect(Token::Colon)
     }
 
     fn parse_left_square(&mut self) -> Result<(), SyntaxError> {
-        match self.next() {
-            Some(Token::LSquare) => Ok(()),
-            Some(token) => Err(SyntaxError::UnexpectedToken(token, Token::LSquare)),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
-        }
+        self.expect(Token::LSquare)
+    }
+
+    fn parse_right_square(&mut self) -> Result<(), SyntaxError> {
+        self.expect(Token::RSquare)
     }
 
     fn parse_dot(&mut self) -> Result<(), SyntaxError> {
+        self.expect(Token::Dot)
+    }
+
+    fn expect(&mut self, expected: Token<'static>) -> Result<(), SyntaxError> {
         match self.next() {
-            Some(Token::Dot) => Ok(()),
-            Some(token) => Err(SyntaxError::UnexpectedToken(token, Token::Dot)),
-            None => Err(SyntaxError::UnexpectedEndOfFile),
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(SyntaxError::UnexpectedToken(
+                expected,
+                token.into_owned(),
+                self.last_span,
+            )),
+            None => Err(SyntaxError::UnexpectedEndOfFile(self.peek_span())),
         }
     }
 
-    fn next(&mut self) -> Option<Token> {
-        self.tokens.next()
+    /// Peeks at the next token, treating `Token::Eof` as the end of input.
+    /// Doc comments are skipped and remembered for the token that follows,
+    /// lexer errors are recorded in `lexer_errors`.
+    fn peek(&mut self) -> Option<&Token<'src>> {
+        loop {
+            match self.tokens.peek() {
+                Some(Err(_)) if self.recover || self.lexer_errors.is_empty() => {
+                    if let Some(Err(err)) = self.tokens.next() {
+                        self.lexer_errors.push(err);
+                    }
+                }
+                Some(Ok(Spanned {
+                    node: Token::DocComment(_),
+                    ..
+                })) => {
+                    if let Some(Ok(Spanned {
+                        node: Token::DocComment(doc),
+                        ..
+                    })) = self.tokens.next()
+                    {
+                        self.leading_doc = Some(doc);
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        if !self.recover && !self.lexer_errors.is_empty() {
+            return None;
+        }
+
+        match self.tokens.peek() {
+            Some(Ok(token)) if token.node != Token::Eof => Some(&token.node),
+            _ => None,
+        }
+    }
+
+    /// Span of the next token, or of the end of input.
+    fn peek_span(&mut self) -> Span {
+        self.peek();
+
+        match self.tokens.peek() {
+            Some(Ok(token)) => token.span,
+            Some(Err(err)) => err.span(),
+            None => Span {
+                start: self.last_span.end,
+                ..self.last_span
+            },
+        }
+    }
+
+    fn next(&mut self) -> Option<Token<'src>> {
+        self.peek()?;
+
+        let token = self.tokens.next()?.ok()?;
+        self.last_span = token.span;
+        self.last_doc = self.leading_doc.take();
+        match token.node {
+            Token::LRound => self.brackets.push(Token::RRound),
+            Token::LSquare => self.brackets.push(Token::RSquare),
+            Token::LCurly => self.brackets.push(Token::RCurly),
+            // A closer also closes any brackets left open inside it.
+            Token::RRound | Token::RSquare | Token::RCurly => {
+                if let Some(index) = self
+                    .brackets
+                    .iter()
+                    .rposition(|closer| *closer == token.node)
+                {
+                    self.brackets.truncate(index);
+                }
+            }
+            _ => {}
+        }
+
+        Some(token.node)
+    }
+}
+
+/// Text of a value used as an object key, like JavaScript's property key
+/// conversion, e.g. `created` or `42`.
+pub fn property_key(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Number(value) => value.to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Array(_) | Value::Object(_) => String::new(),
+    }
+}
+
+/// Converts a string, number, boolean or `null` token into a value, handing
+/// any other token back.
+fn scalar_value(token: Token<'_>) -> Result<Value, Token<'_>> {
+    match token {
+        Token::Str(value) => Ok(Value::String(value.into_owned())),
+        Token::Number(value) => Ok(Value::Number(value)),
+        Token::True => Ok(Value::Boolean(true)),
+        Token::False => Ok(Value::Boolean(false)),
+        Token::Ident(ident) if ident == "null" => Ok(Value::Null),
+        token => Err(token),
+    }
+}
+
+fn precision_option(options: &Value) -> Option<u32> {
+    match options.get("precision") {
+        Some(Value::Number(Number::Integer(precision))) => u32::try_from(*precision).ok(),
+        _ => None,
+    }
+}
+
+fn version_option(options: &Value) -> Option<IpVersion> {
+    match options.get("version") {
+        Some(Value::String(version)) if version == "v4" => Some(IpVersion::V4),
+        Some(Value::String(version)) if version == "v6" => Some(IpVersion::V6),
+        _ => None,
     }
 }
@@ -0,0 +1,27 @@
+use crate::lexer::Number;
+
+/// A literal JavaScript value written in a schema, e.g. the argument of
+/// `.default(...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Value>),
+    /// Object literal, keys in source order.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Value of `key` if this is an object that has it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
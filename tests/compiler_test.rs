@@ -0,0 +1,489 @@
+#[cfg(test)]
+mod tests {
+    use rust_ts_json_compiler::{
+        get_syntax_tree, get_syntax_tree_recovering, to_json, to_json_variants, to_json_with,
+        JsonOptions,
+    };
+
+    #[test]
+    fn test_to_json() {
+        let data = vec![
+            (
+                "z.object({ id: z.number().int(), tags: z.array(z.string()) })",
+                r#"{"id": 1, "tags": ["string"]}"#,
+            ),
+            (
+                "z.object({ назва: z.literal(\"Привіт 👋\"), status: z.enum(['café', 'bar']) })",
+                r#"{"назва": "Привіт 👋", "status": "café"}"#,
+            ),
+            (
+                r#"z.object({ quote: z.literal("say \"hi\"\n"), tab: z.enum([`a\tb`]) })"#,
+                r#"{"quote": "say \"hi\"\n", "tab": "a\tb"}"#,
+            ),
+            (
+                "z.object({ a: z.literal(42), b: z.literal(-1.5), c: z.literal(true), d: z.literal(null), e: z.literal(10n), f: z.literal('42') })",
+                r#"{"a": 42, "b": -1.5, "c": true, "d": null, "e": 10, "f": "42"}"#,
+            ),
+            (
+                "enum Status { Created = 'CREATED', Paid = 'PAID' } enum Level { Low = 1, High } z.object({ status: z.nativeEnum(Status), level: z.nativeEnum(Level) })",
+                r#"{"status": "CREATED", "level": 1}"#,
+            ),
+            (
+                "z.object({ point: z.tuple([z.number(), z.string()]), flags: z.tuple([]).rest(z.boolean()) })",
+                r#"{"point": [1, "string"], "flags": [true]}"#,
+            ),
+            (
+                "z.object({ prices: z.record(z.number()), byId: z.record(z.string().uuid(), z.boolean()), byCode: z.record(z.number().min(7), z.string()) })",
+                r#"{"prices": {"string": 1}, "byId": {"aa5ac446-7e1d-11ee-b962-0242ac120002": true}, "byCode": {"7": "string"}}"#,
+            ),
+            (
+                "z.object({ lookup: z.map(z.number(), z.object({ a: z.string() })), ids: z.set(z.number().int()) })",
+                r#"{"lookup": [[1, {"a": "string"}]], "ids": [1]}"#,
+            ),
+            (
+                "z.object({ a: z.string().refine((v) => v.length > 3), b: z.number() })",
+                r#"{"a": "string", "b": 1}"#,
+            ),
+            (
+                "z.object({ a: z.number().refine((n) => n / 2 >= 1 && n % 2 !== 0, { message: 'odd' }) })",
+                r#"{"a": 1}"#,
+            ),
+            (
+                "z.object({ a: z.array(z.string()).default(() => []) })",
+                r#"{"a": ["string"]}"#,
+            ),
+            ("z.object({a: z.string()});", r#"{"a": "string"}"#),
+        ];
+
+        for (schema, expected) in data {
+            let zod = get_syntax_tree(schema).unwrap();
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_to_json_optional_fields() {
+        let schema = "z.object({
+  id: z.number(),
+  comment: z.string().optional(),
+  parent: z.number().nullable(),
+  note: z.string().nullish(),
+  tags: z.array(z.string().nullable()).optional().nullable()
+})";
+        let zod = get_syntax_tree(schema).unwrap();
+
+        let data = vec![
+            (
+                JsonOptions::default(),
+                r#"{"id": 1, "comment": "string", "parent": 1, "note": "string", "tags": ["string"]}"#,
+            ),
+            (
+                JsonOptions {
+                    omit_optional: true,
+                    ..Default::default()
+                },
+                r#"{"id": 1, "parent": 1}"#,
+            ),
+            (
+                JsonOptions {
+                    null_for_nullable: true,
+                    ..Default::default()
+                },
+                r#"{"id": 1, "comment": "string", "parent": null, "note": null, "tags": null}"#,
+            ),
+        ];
+
+        for (options, expected) in data {
+            assert_eq!(
+                to_json_with(&zod, &options),
+                expected,
+                "options={options:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_json_defaults() {
+        let schema = r#"enum Role { Admin = "ADMIN" }
+z.object({
+  page: z.number().int().default(1),
+  status: z.enum(["ACTIVE", "CLOSED"]).default("CLOSED"),
+  ratio: z.number().optional().default(-0.5),
+  tags: z.array(z.string()).default(["a", 'b"c']),
+  filter: z.any().default({ active: true, "owner id": null, ids: [1n, 2,] }),
+  created: z.date().default(new Date()),
+  role: z.nativeEnum(Role).default(Role.Admin),
+  ids: z.array(z.number()).default([Role.Admin]),
+  total: z.number().default(z.number()),
+})"#;
+        let zod = get_syntax_tree(schema).unwrap();
+
+        assert_eq!(
+            to_json(&zod),
+            r#"{"page": 1, "status": "CLOSED", "ratio": -0.5, "tags": ["a", "b\"c"], "filter": {"active": true, "owner id": null, "ids": [1, 2]}, "created": "2023-11-08T12:00:00.000Z", "role": "ADMIN", "ids": [1], "total": 1}"#
+        );
+    }
+
+    #[test]
+    fn test_to_json_number_checks() {
+        let data = vec![
+            ("z.number()", "1"),
+            ("z.number().int()", "1"),
+            ("z.number().min(100)", "100"),
+            ("z.number().gt(100).int()", "101"),
+            ("z.number().gt(1)", "2"),
+            ("z.number().negative()", "-1"),
+            ("z.number().int().nonpositive()", "0"),
+            ("z.number().positive().lt(1)", "0.5"),
+            ("z.number().multipleOf(5)", "5"),
+            ("z.number().multipleOf(0.1).min(0.25).max(0.7)", "0.7"),
+            ("z.number().int().multipleOf(2.5)", "5"),
+            ("z.number().lte(-10.5, { message: \"too big\" })", "-10.5"),
+            ("z.number().int().gte(-20).lt(-7).multipleOf(3)", "-9"),
+            (
+                "z.number().int().safe().gt(9007199254740990)",
+                "9007199254740991",
+            ),
+            ("z.number().finite().max(0.5)", "0.5"),
+        ];
+
+        for (schema, expected) in data {
+            let zod = get_syntax_tree(schema).unwrap();
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_to_json_string_checks() {
+        let data = vec![
+            ("z.string()", r#""string""#),
+            ("z.string().min(10)", r#""stringstri""#),
+            ("z.string().max(3)", r#""str""#),
+            ("z.string().max(1000000000000)", r#""string""#),
+            ("z.string().length(8).toUpperCase()", r#""STRINGST""#),
+            ("z.string().min(1e1)", r#""stringstri""#),
+            ("z.string().length(2.0)", r#""st""#),
+            (r#"z.string().startsWith("https://")"#, r#""https://""#),
+            (
+                r#"z.string().startsWith("ID-").endsWith("!").includes("x").min(8)"#,
+                r#""ID-xstr!""#,
+            ),
+            (r#"z.string().includes("ab").includes("b")"#, r#""abb""#),
+            (
+                r#"z.string().nonempty().includes("ab").endsWith("ab")"#,
+                r#""ab""#,
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let zod = get_syntax_tree(schema).unwrap();
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_to_json_string_formats() {
+        let data = vec![
+            ("z.string().email()", r#""admin@admin.com""#),
+            (
+                "z.string().uuid()",
+                r#""aa5ac446-7e1d-11ee-b962-0242ac120002""#,
+            ),
+            ("z.string().url()", r#""https://example.com""#),
+            ("z.string().emoji()", r#""😀""#),
+            ("z.string().nanoid()", r#""V1StGXR8_Z5jdHi6B-myT""#),
+            ("z.string().cuid()", r#""cjld2cjxh0000qzrmn831i7rn""#),
+            ("z.string().cuid2()", r#""tz4a98xxat96iws9zmbrgj3a""#),
+            ("z.string().ulid()", r#""01ARZ3NDEKTSV4RRFFQ69G5FAV""#),
+            ("z.string().datetime()", r#""2023-11-08T12:00:00Z""#),
+            (
+                "z.string().datetime({ offset: true, precision: 3 })",
+                r#""2023-11-08T12:00:00.000Z""#,
+            ),
+            ("z.string().date()", r#""2023-11-08""#),
+            ("z.string().time({ precision: 2 })", r#""12:00:00.00""#),
+            ("z.string().duration()", r#""P3Y6M4DT12H30M5S""#),
+            (r#"z.string().ip({ version: "v4" })"#, r#""192.168.0.1""#),
+            (r#"z.string().ip({ version: "v6" })"#, r#""2001:db8::1""#),
+            ("z.string().cidr()", r#""192.168.0.0/24""#),
+            ("z.string().base64()", r#""c3RyaW5n""#),
+            ("z.string().base64url()", r#""c3RyaW5n""#),
+            (r"z.string().regex(/^[A-Z]{3}-\d{4}$/i)", r#""ABC-0123""#),
+            (r"z.string().email().regex(/^x$/)", r#""admin@admin.com""#),
+            (
+                r#"z.string().email().endsWith("@corp.com")"#,
+                r#""admin@corp.com""#,
+            ),
+            (
+                r#"z.string().email().endsWith(".org")"#,
+                r#""admin@admin.org""#,
+            ),
+            (
+                r#"z.string().email().startsWith("bob")"#,
+                r#""bob@admin.com""#,
+            ),
+            (
+                r#"z.string().email().startsWith("bob@")"#,
+                r#""bob@admin.com""#,
+            ),
+            (r#"z.string().email().includes("+x")"#, r#""+x@admin.com""#),
+            ("z.string().email().min(20)", r#""adminaaaaa@admin.com""#),
+            ("z.string().email().max(8)", r#""a@a.co""#),
+            (
+                r#"z.string().url().endsWith("/api")"#,
+                r#""https://example.com/api""#,
+            ),
+            (
+                r#"z.string().url().startsWith("http://")"#,
+                r#""http://example.com""#,
+            ),
+            (
+                r#"z.string().url().startsWith("https://api.")"#,
+                r#""https://api.example.com""#,
+            ),
+            (
+                r#"z.string().url().includes("v1").endsWith(".json")"#,
+                r#""https://example.com/v1.json""#,
+            ),
+            ("z.string().url().min(25)", r#""https://example.com/aaaaa""#),
+            ("z.string().url().max(12)", r#""https://a.co""#),
+            (
+                r#"z.string().uuid().startsWith("bb")"#,
+                r#""bb5ac446-7e1d-11ee-b962-0242ac120002""#,
+            ),
+            ("z.string().base64().min(20)", r#""c3RyaW5naaaaaaaaaaaa""#),
+            ("z.string().base64url().min(9)", r#""c3RyaW5naa""#),
+            (
+                "z.string().cuid2().min(30)",
+                r#""tz4a98xxat96iws9zmbrgj3aaaaaaa""#,
+            ),
+            ("z.string().cuid2().max(4)", r#""tz4a""#),
+            (r#"z.string().date().startsWith("2024")"#, r#""2024-11-08""#),
+            (r#"z.string().ip().startsWith("10.")"#, r#""10.168.0.1""#),
+            (
+                r#"z.string().datetime().endsWith(":30Z")"#,
+                r#""2023-11-08T12:00:30Z""#,
+            ),
+            (r"z.string().length(6).regex(/^\d+$/)", r#""012345""#),
+            (
+                r"z.string().regex(/^[a-z0-9_]+$/).min(3).max(20)",
+                r#""abc""#,
+            ),
+            (
+                r#"z.string().regex(/^[a-z]+$/).min(4).startsWith("a")"#,
+                r#""abcd""#,
+            ),
+            (r"z.string().regex(/^[A-Z]/).min(8)", r#""Astrings""#),
+            (r#"z.string().regex(/ab/).endsWith("z")"#, r#""abz""#),
+            (
+                r#"z.string().regex(/^[a-z]+\/\d$/).includes("b")"#,
+                r#""ba/0""#,
+            ),
+            (r#"z.string().regex(/^[a-z]+$/).startsWith("x")"#, r#""x""#),
+            (
+                r"z.string().regex(/^(?=.*[A-Z])(?=.*\d).{8,}$/)",
+                r#""A0aaaaaa""#,
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let zod = get_syntax_tree(schema).unwrap();
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+
+        let zod = get_syntax_tree("z.string().min(20000)").unwrap();
+        assert_eq!(to_json(&zod).len(), 20002);
+
+        let zod = get_syntax_tree(r"z.string().regex(/^(?=.*[A-Z])(?=.*\d).{8,}$/).min(5000)");
+        let json = to_json(&zod.unwrap());
+        assert!(
+            json.starts_with(r#""A0a"#) && json.len() == 5002,
+            "json={json:?}"
+        );
+    }
+
+    #[test]
+    fn test_to_json_special_types() {
+        let schema = r#"z.object({
+  a: z.null(),
+  b: z.undefined(),
+  c: z.void(),
+  d: z.never(),
+  e: z.unknown(),
+  f: z.nan(),
+  g: z.bigint(),
+  h: z.date(),
+  i: z.date().min(new Date("2024-02-29")),
+  j: z.date().max(new Date("2020-01-01T10:30:00.5+02:00"), "too late"),
+  k: z.symbol(),
+  l: z.tuple([z.undefined(), z.string()]),
+})"#;
+        let zod = get_syntax_tree(schema).unwrap();
+
+        assert_eq!(
+            to_json(&zod),
+            r#"{"a": null, "e": {}, "f": null, "g": 1, "h": "2023-11-08T12:00:00.000Z", "i": "2024-02-29T00:00:00.000Z", "j": "2020-01-01T08:30:00.500Z", "l": [null, "string"]}"#
+        );
+    }
+
+    #[test]
+    fn test_to_json_variants() {
+        let schema = r#"z.discriminatedUnion("type", [
+  z.object({ type: z.literal("created"), id: z.number().int() }),
+  z.object({ type: z.literal("paid"), amount: z.number(), note: z.string().optional() }),
+])"#;
+        let zod = get_syntax_tree(schema).unwrap();
+        let options = JsonOptions {
+            omit_optional: true,
+            ..JsonOptions::default()
+        };
+
+        assert_eq!(to_json(&zod), r#"{"type": "created", "id": 1}"#);
+        assert_eq!(
+            to_json_variants(&zod, &options).unwrap(),
+            r#"{"created": {"type": "created", "id": 1}, "paid": {"type": "paid", "amount": 1}}"#
+        );
+
+        let zod = get_syntax_tree("z.union([z.string(), z.number()])").unwrap();
+        assert_eq!(to_json_variants(&zod, &options), None);
+    }
+
+    #[test]
+    fn test_to_json_intersection() {
+        let data = vec![
+            (
+                "z.intersection(z.object({ id: z.number().int() }), z.object({ name: z.string() }))",
+                r#"{"id": 1, "name": "string"}"#,
+            ),
+            (
+                "z.object({ a: z.string(), b: z.any() }).and(z.object({ a: z.literal('x'), b: z.boolean() })).and(z.object({ c: z.null() }))",
+                r#"{"a": "x", "b": true, "c": null}"#,
+            ),
+            (
+                "z.object({ user: z.object({ id: z.number() }) }).and(z.object({ user: z.object({ name: z.string() }) }))",
+                r#"{"user": {"id": 1, "name": "string"}}"#,
+            ),
+            ("z.string().and(z.unknown())", r#""string""#),
+            (
+                "z.object({ a: z.string() }).and(z.object({ a: z.string().min(10) }))",
+                r#"{"a": "stringstri"}"#,
+            ),
+            (
+                "z.object({ a: z.string().min(20) }).and(z.object({ a: z.string().email() }))",
+                r#"{"a": "adminaaaaa@admin.com"}"#,
+            ),
+            (
+                "z.object({ a: z.number() }).and(z.object({ a: z.number().min(5) }))",
+                r#"{"a": 5}"#,
+            ),
+            (
+                r#"z.string().startsWith("ab").and(z.unknown()).and(z.string().endsWith("z"))"#,
+                r#""abz""#,
+            ),
+            (
+                "z.object({ a: z.bigint() }).and(z.object({ a: z.literal(10n) }))",
+                r#"{"a": 10}"#,
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let zod = get_syntax_tree(schema).unwrap();
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+
+        let zod = get_syntax_tree(
+            "z.object({ a: z.string().optional() }).and(z.object({ a: z.string(), b: z.number().optional() }))",
+        )
+        .unwrap();
+        let options = JsonOptions {
+            omit_optional: true,
+            ..JsonOptions::default()
+        };
+        assert_eq!(to_json_with(&zod, &options), r#"{"a": "string"}"#);
+    }
+
+    #[test]
+    fn test_to_json_object_methods() {
+        let user = "z.object({ id: z.number().int(), name: z.string(), email: z.string().email().optional() })";
+        let data = vec![
+            (".extend({ name: z.literal('root'), admin: z.boolean() })", r#"{"id": 1, "name": "root", "admin": true}"#),
+            (".merge(z.object({ id: z.boolean() }))", r#"{"id": true, "name": "string"}"#),
+            (".pick({ id: true, email: true })", r#"{"id": 1}"#),
+            (".omit({ id: true })", r#"{"name": "string"}"#),
+            (".partial()", r#"{}"#),
+            (".partial({ name: true })", r#"{"id": 1}"#),
+            (".partial().required({ id: true })", r#"{"id": 1}"#),
+            (".required()", r#"{"id": 1, "name": "string", "email": "admin@admin.com"}"#),
+            (".extend({ tags: z.array(z.object({ v: z.number() })) }).deepPartial().required({ tags: true })", r#"{"tags": [{}]}"#),
+        ];
+        let options = JsonOptions {
+            omit_optional: true,
+            ..JsonOptions::default()
+        };
+
+        for (methods, expected) in data {
+            let schema = format!("{user}{methods}");
+            let zod = get_syntax_tree(&schema).unwrap();
+            assert_eq!(to_json_with(&zod, &options), expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_to_json_unknown_keys() {
+        let data = vec![
+            ("z.object({ a: z.number() }).strict()", r#"{"a": 1}"#),
+            ("z.object({ a: z.number() }).passthrough()", r#"{"a": 1}"#),
+            (
+                "z.object({ a: z.number() }).catchall(z.boolean())",
+                r#"{"a": 1, "string": true}"#,
+            ),
+            (
+                "z.object({ string: z.number() }).catchall(z.boolean())",
+                r#"{"string": 1}"#,
+            ),
+            ("z.object({}).catchall(z.never())", r#"{}"#),
+        ];
+
+        for (schema, expected) in data {
+            let zod = get_syntax_tree(schema).unwrap();
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_to_json_object_keys() {
+        let schema = r#"const KEY = "x-key";
+z.object({
+  "content-type": z.literal("application/json"),
+  'say "hi"': z.boolean(),
+  "back\\slash": z.null(),
+  1: z.number(),
+  [KEY]: z.string(),
+})"#;
+        let zod = get_syntax_tree(schema).unwrap();
+
+        assert_eq!(
+            to_json(&zod),
+            r#"{"content-type": "application/json", "say \"hi\"": true, "back\\slash": null, "1": 1, "x-key": "string"}"#
+        );
+    }
+
+    #[test]
+    fn test_to_json_recovered() {
+        let data = vec![
+            ("z.enum([1])", "null"),
+            ("z.union([z.strin()])", "null"),
+            ("z.union([1])", "null"),
+            (
+                "z.object({ a: z.enum([1]), b: z.strin(), c: z.number() })",
+                r#"{"a": null, "b": null, "c": 1}"#,
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let (zod, errors) = get_syntax_tree_recovering(schema);
+            assert!(!errors.is_empty(), "schema={schema:?}");
+            assert_eq!(to_json(&zod), expected, "schema={schema:?}");
+        }
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use rust_ts_json_compiler::{diagnostics, get_syntax_tree};
+
+    #[test]
+    fn test_render() {
+        let data = vec![
+            (
+                "z.object({\n  a: z.strin(),\n})",
+                "error: unknown zod type `strin`
+ --> 2:8
+  |
+2 |   a: z.strin(),
+  |        ^^^^^ not a zod type
+  |
+  = help: did you mean `z.string()`?
+",
+            ),
+            (
+                "z.object({\n\ta:\tz.strin(),\n})",
+                "error: unknown zod type `strin`
+ --> 2:7
+  |
+2 |     a:    z.strin(),
+  |             ^^^^^ not a zod type
+  |
+  = help: did you mean `z.string()`?
+",
+            ),
+            (
+                "z.array(z.number()]",
+                "error: expected `)`, found `]`
+ --> 1:19
+  |
+1 | z.array(z.number()]
+  |                   ^ expected `)`
+",
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(diagnostics::render(schema, &err), expected);
+        }
+    }
+}
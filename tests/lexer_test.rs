@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use rust_ts_json_compiler::lexer::{Lexer, Token};
+    use std::borrow::Cow;
+
+    use rust_ts_json_compiler::lexer::{Lexer, LexerError, Number, Span, Token};
 
     #[test]
     fn test_next_token() {
@@ -25,137 +27,137 @@ mod tests {
 })";
         let mut lex = Lexer::new(schema);
         let data = vec![
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("object".to_string()),
+            Token::Ident("object".into()),
             Token::LRound,
             Token::LCurly,
-            Token::Ident("products".to_string()),
+            Token::Ident("products".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("array".to_string()),
+            Token::Ident("array".into()),
             Token::LRound,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("object".to_string()),
+            Token::Ident("object".into()),
             Token::LRound,
             Token::LCurly,
-            Token::Ident("productId".to_string()),
+            Token::Ident("productId".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("number".to_string()),
+            Token::Ident("number".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("int".to_string()),
+            Token::Ident("int".into()),
             Token::LRound,
             Token::RRound,
             Token::Comma,
-            Token::Ident("amount".to_string()),
+            Token::Ident("amount".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("number".to_string()),
+            Token::Ident("number".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("multipleOf".to_string()),
+            Token::Ident("multipleOf".into()),
             Token::LRound,
-            Token::Int("1".to_string()),
+            Token::Number(Number::Integer(1)),
             Token::RRound,
             Token::RCurly,
             Token::RRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("min".to_string()),
+            Token::Ident("min".into()),
             Token::LRound,
-            Token::Int("1".to_string()),
+            Token::Number(Number::Integer(1)),
             Token::RRound,
             Token::Comma,
-            Token::Ident("details".to_string()),
+            Token::Ident("details".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("object".to_string()),
+            Token::Ident("object".into()),
             Token::LRound,
             Token::LCurly,
-            Token::Ident("firstName".to_string()),
+            Token::Ident("firstName".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("string".to_string()),
+            Token::Ident("string".into()),
             Token::LRound,
             Token::RRound,
             Token::Comma,
-            Token::Ident("lastName".to_string()),
+            Token::Ident("lastName".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("string".to_string()),
+            Token::Ident("string".into()),
             Token::LRound,
             Token::RRound,
             Token::Comma,
-            Token::Ident("email".to_string()),
+            Token::Ident("email".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("string".to_string()),
+            Token::Ident("string".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("email".to_string()),
+            Token::Ident("email".into()),
             Token::LRound,
             Token::RRound,
             Token::Comma,
-            Token::Ident("phone".to_string()),
+            Token::Ident("phone".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("string".to_string()),
+            Token::Ident("string".into()),
             Token::LRound,
             Token::RRound,
             Token::RCurly,
             Token::RRound,
             Token::Comma,
-            Token::Ident("comment".to_string()),
+            Token::Ident("comment".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("string".to_string()),
+            Token::Ident("string".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("optional".to_string()),
+            Token::Ident("optional".into()),
             Token::LRound,
             Token::RRound,
             Token::Comma,
-            Token::Ident("cityId".to_string()),
+            Token::Ident("cityId".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("number".to_string()),
+            Token::Ident("number".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("int".to_string()),
+            Token::Ident("int".into()),
             Token::LRound,
             Token::RRound,
             Token::Comma,
-            Token::Ident("userId".to_string()),
+            Token::Ident("userId".into()),
             Token::Colon,
-            Token::Ident("z".to_string()),
+            Token::Ident("z".into()),
             Token::Dot,
-            Token::Ident("string".to_string()),
+            Token::Ident("string".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("uuid".to_string()),
+            Token::Ident("uuid".into()),
             Token::LRound,
             Token::RRound,
             Token::Dot,
-            Token::Ident("optional".to_string()),
+            Token::Ident("optional".into()),
             Token::LRound,
             Token::RRound,
             Token::RCurly,
@@ -168,4 +170,296 @@ mod tests {
             assert_eq!(token, t, "test {i} expected={t:?}, got={token:?}");
         }
     }
+
+    #[test]
+    fn test_token_spans() {
+        let schema = "z.object({\n  id: z.number()\n})";
+        let mut lex = Lexer::new(schema);
+        let data = vec![
+            (Token::Ident("z".into()), Span::new(0, 1, 1, 1)),
+            (Token::Dot, Span::new(1, 2, 1, 2)),
+            (Token::Ident("object".into()), Span::new(2, 8, 1, 3)),
+            (Token::LRound, Span::new(8, 9, 1, 9)),
+            (Token::LCurly, Span::new(9, 10, 1, 10)),
+            (Token::Ident("id".into()), Span::new(13, 15, 2, 3)),
+            (Token::Colon, Span::new(15, 16, 2, 5)),
+            (Token::Ident("z".into()), Span::new(17, 18, 2, 7)),
+            (Token::Dot, Span::new(18, 19, 2, 8)),
+            (Token::Ident("number".into()), Span::new(19, 25, 2, 9)),
+            (Token::LRound, Span::new(25, 26, 2, 15)),
+            (Token::RRound, Span::new(26, 27, 2, 16)),
+            (Token::RCurly, Span::new(28, 29, 3, 1)),
+            (Token::RRound, Span::new(29, 30, 3, 2)),
+            (Token::Eof, Span::new(30, 30, 3, 3)),
+        ];
+
+        for (i, (t, span)) in data.into_iter().enumerate() {
+            let token = lex.next_spanned_token();
+            assert_eq!(
+                token.node, t,
+                "test {i} expected={t:?}, got={:?}",
+                token.node
+            );
+            assert_eq!(token.span, span, "test {i} token={t:?}");
+        }
+    }
+
+    #[test]
+    fn test_unicode() {
+        let schema = "z.object({ назва: z.literal(\"Привіт 👋\"), $id: z.enum(['café']) })";
+        let mut lex = Lexer::new(schema);
+        let data = vec![
+            (Token::Ident("z".into()), 1),
+            (Token::Dot, 2),
+            (Token::Ident("object".into()), 3),
+            (Token::LRound, 9),
+            (Token::LCurly, 10),
+            (Token::Ident("назва".into()), 12),
+            (Token::Colon, 17),
+            (Token::Ident("z".into()), 19),
+            (Token::Dot, 20),
+            (Token::Ident("literal".into()), 21),
+            (Token::LRound, 28),
+            (Token::Str("Привіт 👋".into()), 29),
+            (Token::RRound, 39),
+            (Token::Comma, 40),
+            (Token::Ident("$id".into()), 42),
+            (Token::Colon, 45),
+            (Token::Ident("z".into()), 47),
+            (Token::Dot, 48),
+            (Token::Ident("enum".into()), 49),
+            (Token::LRound, 53),
+            (Token::LSquare, 54),
+            (Token::Str("café".into()), 55),
+            (Token::RSquare, 61),
+            (Token::RRound, 62),
+            (Token::RCurly, 64),
+            (Token::RRound, 65),
+            (Token::Eof, 66),
+        ];
+
+        for (i, (t, column)) in data.into_iter().enumerate() {
+            let token = lex.next_spanned_token();
+            assert_eq!(
+                token.node, t,
+                "test {i} expected={t:?}, got={:?}",
+                token.node
+            );
+            assert_eq!(token.span.column, column, "test {i} token={t:?}");
+        }
+    }
+
+    #[test]
+    fn test_comments() {
+        let schema = "// leading
+z /* inline */ . /**/ string /** Doc */ ( // trailing
+)";
+        let mut lex = Lexer::new(schema);
+        let data = vec![
+            Token::Ident("z".into()),
+            Token::Dot,
+            Token::Ident("string".into()),
+            Token::DocComment("Doc".into()),
+            Token::LRound,
+            Token::RRound,
+            Token::Eof,
+        ];
+
+        for (i, t) in data.into_iter().enumerate() {
+            let token = lex.next_token();
+            assert_eq!(token, t, "test {i} expected={t:?}, got={token:?}");
+        }
+
+        let err = Lexer::new("z.string() /* never closed")
+            .tokenize()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            LexerError::UnterminatedComment(Span::new(11, 26, 1, 12))
+        );
+    }
+
+    #[test]
+    fn test_strings() {
+        let data = vec![
+            (r#""plain""#, Ok(Token::Str("plain".into()))),
+            (r#"'it\'s'"#, Ok(Token::Str("it's".into()))),
+            (
+                r#""a\"b\\c\n\t\x41B\u{1F600}😀\0""#,
+                Ok(Token::Str("a\"b\\c\n\tAB😀😀\0".into())),
+            ),
+            (
+                "'line \\\ncontinued'",
+                Ok(Token::Str("line continued".into())),
+            ),
+            (
+                "`multi\nline $ {}`",
+                Ok(Token::Str("multi\nline $ {}".into())),
+            ),
+            (
+                r#""never closed"#,
+                Err(LexerError::UnterminatedString(Span::new(0, 13, 1, 1))),
+            ),
+            (
+                "'no newline\n'",
+                Err(LexerError::UnterminatedString(Span::new(0, 11, 1, 1))),
+            ),
+            (
+                r#""bad \xZZ escape""#,
+                Err(LexerError::InvalidEscape(Span::new(5, 7, 1, 6))),
+            ),
+            (
+                "`hello ${name}`",
+                Err(LexerError::TemplateInterpolation(Span::new(7, 9, 1, 8))),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let tokens = Lexer::new(schema).tokenize();
+            let token = tokens.map(|tokens| tokens.into_iter().next().unwrap().node);
+            assert_eq!(token, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        let data = vec![
+            ("42", Ok(Number::Integer(42))),
+            ("-10", Ok(Number::Integer(-10))),
+            ("1.5", Ok(Number::Float(1.5))),
+            (".5", Ok(Number::Float(0.5))),
+            ("-.25", Ok(Number::Float(-0.25))),
+            ("1e3", Ok(Number::Float(1000.0))),
+            ("2.5E-3", Ok(Number::Float(0.0025))),
+            ("1e+6", Ok(Number::Float(1e6))),
+            ("0xFF", Ok(Number::Integer(255))),
+            ("0o17", Ok(Number::Integer(15))),
+            ("0b1010", Ok(Number::Integer(10))),
+            ("1_000_000", Ok(Number::Integer(1_000_000))),
+            ("10n", Ok(Number::BigInt(10))),
+            ("-0x10n", Ok(Number::BigInt(-16))),
+            (
+                "18446744073709551616",
+                Ok(Number::Float(18446744073709551616.0)),
+            ),
+            (
+                "1.2.3",
+                Err(LexerError::InvalidNumber(Span::new(0, 5, 1, 1))),
+            ),
+            (
+                "1__0",
+                Err(LexerError::InvalidNumber(Span::new(0, 4, 1, 1))),
+            ),
+            ("1_", Err(LexerError::InvalidNumber(Span::new(0, 2, 1, 1)))),
+            ("0xZ", Err(LexerError::InvalidNumber(Span::new(0, 3, 1, 1)))),
+            (
+                "1.5n",
+                Err(LexerError::InvalidNumber(Span::new(0, 4, 1, 1))),
+            ),
+            ("017", Err(LexerError::InvalidNumber(Span::new(0, 3, 1, 1)))),
+            ("3px", Err(LexerError::InvalidNumber(Span::new(0, 3, 1, 1)))),
+        ];
+
+        for (schema, expected) in data {
+            let tokens = Lexer::new(schema).tokenize();
+            let token = tokens.map(|tokens| tokens.into_iter().next().unwrap().node);
+            assert_eq!(token, expected.map(Token::Number), "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_borrowed_tokens() {
+        let schema = r#"z.literal("plain", "esc\n", true)"#;
+        let tokens: Vec<Token> = Lexer::new(schema)
+            .map(|token| token.unwrap().node)
+            .collect();
+
+        assert!(matches!(tokens[0], Token::Ident(Cow::Borrowed("z"))));
+        assert!(matches!(tokens[2], Token::Ident(Cow::Borrowed("literal"))));
+        assert!(matches!(tokens[4], Token::Str(Cow::Borrowed("plain"))));
+        assert!(matches!(&tokens[6], Token::Str(Cow::Owned(value)) if value == "esc\n"));
+        assert_eq!(tokens[8], Token::True);
+        assert_eq!(tokens.last(), Some(&Token::Eof));
+    }
+
+    #[test]
+    fn test_punctuation() {
+        let data = vec![
+            (
+                "(v) => v / 2",
+                vec![
+                    Token::LRound,
+                    Token::Ident("v".into()),
+                    Token::RRound,
+                    Token::Equals,
+                    Token::Punct('>'),
+                    Token::Ident("v".into()),
+                    Token::Punct('/'),
+                    Token::Number(Number::Integer(2)),
+                    Token::Eof,
+                ],
+            ),
+            (
+                "a && !b, /x/",
+                vec![
+                    Token::Ident("a".into()),
+                    Token::Punct('&'),
+                    Token::Punct('&'),
+                    Token::Punct('!'),
+                    Token::Ident("b".into()),
+                    Token::Comma,
+                    Token::Regex("/x/".into()),
+                    Token::Eof,
+                ],
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let tokens: Vec<Token> = Lexer::new(schema)
+                .tokenize()
+                .unwrap()
+                .into_iter()
+                .map(|token| token.node)
+                .collect();
+            assert_eq!(tokens, expected, "schema={schema:?}");
+        }
+
+        assert_eq!(
+            Lexer::new("§").tokenize(),
+            Err(LexerError::IllegalCharacter('§', Span::new(0, 2, 1, 1)))
+        );
+    }
+
+    #[test]
+    fn test_regex() {
+        let data = vec![
+            (
+                r"/^[A-Z]{3}-\d{4}$/",
+                Ok(Token::Regex(r"/^[A-Z]{3}-\d{4}$/".into())),
+            ),
+            (r"/[/]\//gi", Ok(Token::Regex(r"/[/]\//gi".into()))),
+            (
+                "/never closed",
+                Err(LexerError::UnterminatedRegex(Span::new(0, 13, 1, 1))),
+            ),
+            (
+                r"/a\",
+                Err(LexerError::UnterminatedRegex(Span::new(0, 3, 1, 1))),
+            ),
+            (
+                "/a/gg",
+                Err(LexerError::InvalidRegexFlags(Span::new(3, 5, 1, 4))),
+            ),
+            (
+                "/a/x",
+                Err(LexerError::InvalidRegexFlags(Span::new(3, 4, 1, 4))),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let tokens = Lexer::new(schema).tokenize();
+            let token = tokens.map(|tokens| tokens.into_iter().next().unwrap().node);
+            assert_eq!(token, expected, "schema={schema:?}");
+        }
+    }
 }
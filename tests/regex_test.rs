@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use rust_ts_json_compiler::regex::{self, RegexError};
+
+    #[test]
+    fn test_example() {
+        let data = vec![
+            (r"^[A-Z]{3}-\d{4}$", "ABC-0123"),
+            (r"^\+?\d{10,15}$", "+0123456789"),
+            (r"^[a-z][a-z0-9_]*$", "aa"),
+            (r"^(?:foo|bar)+\.(com|org)$", "foo.com"),
+            (r"(?<year>\d{4})-\k<year>", "0123-0123"),
+            (r"([ab])\1", "aa"),
+            (r"^[^a-z0-9]{2}x?$", "ABx"),
+            (r"\w\W\s\S\D", "a! aa"),
+            (r"[\d-]{3}", "-01"),
+            (r"a{2,}b{,3}c{0}", "aab{,3}"),
+            (r"\x41B\u{1F600}\t[\]]", "AB😀\t]"),
+            (r"\bcolou?r(?=s)(?!x)", "colours"),
+            (r"^\p{Lu}\p{N}.$", "A0a"),
+            (r"[]|x", "x"),
+            (r"^(?=.*[A-Z])(?=.*\d).{8,}$", "A0aaaaaa"),
+            (r"^(?!admin$)[a-z]+$", "a"),
+            (r"(?<=\$)\d+", "$0"),
+            (r"^(?=.*\d)(?:a|b).+$", "a0"),
+        ];
+
+        for (pattern, expected) in data {
+            let example = regex::parse(pattern).map(|pattern| pattern.example());
+            assert_eq!(
+                example,
+                Ok(Some(expected.to_string())),
+                "pattern={pattern:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_example_with_length() {
+        let data = vec![
+            (r"^\d+$", 6, 6, Some("012345")),
+            (r"^[a-z0-9_]+$", 3, 20, Some("abc")),
+            (r"^[a-z]?\d*x?$", 0, 0, Some("")),
+            (r"^(ab)+$", 5, 7, Some("ababab")),
+            (r"^a{2,3}$", 5, 10, None),
+            (r"^a{4}$", 0, 3, None),
+            (r"^(?=.*\d)[a-z\d]{6,}$", 8, 8, Some("0bcdefgh")),
+            (r"^[^\w!-@]$", 0, 10, Some("[")),
+            (r"^[^\w!-@]{2}$", 0, 10, Some("[\\")),
+            (r"^a{3}$", 10_001, 20_000, None),
+        ];
+
+        for (pattern, min, max, expected) in data {
+            let example =
+                regex::parse(pattern).map(|pattern| pattern.example_with_length(min, max));
+            assert_eq!(
+                example,
+                Ok(expected.map(str::to_string)),
+                "pattern={pattern:?} min={min} max={max}"
+            );
+        }
+
+        let pattern = regex::parse("^a{1001}$").unwrap();
+        assert_eq!(pattern.example(), Some("a".repeat(1001)));
+
+        let pattern = regex::parse(r"^(?=.*[A-Z])(?=.*\d).{8,}$").unwrap();
+        let example = pattern.example_with_length(10_000, 10_000).unwrap();
+        assert!(example.starts_with("A0a"), "example={example:?}");
+
+        let pattern = regex::parse(r"^(ab)+$").unwrap();
+        assert_eq!(
+            pattern.example_with_length(10_000, 10_000),
+            Some("ab".repeat(5000))
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let data = vec![r"^(?=\d)[a-z]$", r"^(?!a)a$", r"(?=x)y"];
+
+        for pattern in data {
+            let example = regex::parse(pattern).map(|pattern| pattern.example());
+            assert_eq!(example, Ok(None), "pattern={pattern:?}");
+        }
+    }
+
+    #[test]
+    fn test_is_match() {
+        let data = vec![
+            (r"^(?=.*[A-Z])(?=.*\d).{8,}$", "A0aaaaaa", true),
+            (r"^(?=.*[A-Z])(?=.*\d).{8,}$", "aaaaaaaa", false),
+            (r"colou?r", "my colour", true),
+            (r"^(a|ab)c$", "abc", true),
+            (r"(\w)\1", "xyzz", true),
+            (r"\bfoo\b", "foobar", false),
+            (r"(?<!\$)\b\d+", "$5", false),
+            (r"^(a*)*$", "aaaa", true),
+            (r"^a{2,3}b$", "aaab", true),
+            (r"^a{2,3}b$", "aaaab", false),
+            (r"^[^\d]+x", "abcx1", true),
+        ];
+
+        for (pattern, text, expected) in data {
+            let pattern = regex::parse(pattern).unwrap();
+            assert_eq!(pattern.is_match(text), expected, "text={text:?}");
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let data = vec![
+            ("(a", RegexError::UnmatchedParen),
+            ("a)", RegexError::UnmatchedParen),
+            ("[a-", RegexError::UnterminatedClass),
+            ("*a", RegexError::NothingToRepeat),
+            ("a**", RegexError::NothingToRepeat),
+            ("a{3,1}", RegexError::InvalidQuantifier),
+            ("[z-a]", RegexError::InvalidRange),
+            ("(?x)", RegexError::InvalidGroup),
+            (r"\x4", RegexError::InvalidEscape),
+            ("a\\", RegexError::InvalidEscape),
+            (r"(a)\2", RegexError::InvalidBackreference),
+            (r"\k<name>(?<other>a)", RegexError::InvalidBackreference),
+            ("x{100000000}", RegexError::RepetitionTooLarge),
+            ("(x{1000}){11}", RegexError::RepetitionTooLarge),
+            ("x{10001}", RegexError::RepetitionTooLarge),
+        ];
+
+        for (pattern, expected) in data {
+            assert_eq!(regex::parse(pattern), Err(expected), "pattern={pattern:?}");
+        }
+    }
+}
@@ -0,0 +1,930 @@
+#[cfg(test)]
+mod tests {
+    use rust_ts_json_compiler::{
+        get_syntax_tree, get_syntax_tree_recovering,
+        lexer::{LexerError, Number, Span, Token},
+        regex::RegexError,
+        syntax_tree::{
+            format_date, parse_date, DateChecks, DatetimeOptions, IpVersion, Regex, StringChecks,
+            StringFormat, StringTransform, SyntaxError, UnknownKeys, Value, ZodExpression,
+        },
+        Error,
+    };
+
+    #[test]
+    fn test_node_spans() {
+        let schema = "z.object({\n  ids: z.array(z.number().int()),\n  name: z.string()\n})";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        assert_eq!(tree.span, Span::new(0, schema.len(), 1, 1));
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+
+        let (key, ids) = (&fields[0].key, &fields[0].value);
+        assert_eq!(key.node, "ids");
+        assert_eq!(key.span, Span::new(13, 16, 2, 3));
+        assert_eq!(
+            &schema[ids.span.start..ids.span.end],
+            "z.array(z.number().int())"
+        );
+
+        let ZodExpression::Array(item) = &ids.node else {
+            panic!("expected array, got={:?}", ids.node);
+        };
+        assert_eq!(&schema[item.span.start..item.span.end], "z.number().int()");
+        assert_eq!((item.span.line, item.span.column), (2, 16));
+
+        let (key, name) = (&fields[1].key, &fields[1].value);
+        assert_eq!(key.node, "name");
+        assert_eq!(&schema[name.span.start..name.span.end], "z.string()");
+    }
+
+    #[test]
+    fn test_scope_ends() {
+        let schema = "z.object({ a: z.any().describe(f(x, y)), b: z.array(z.boolean()), c: z.literal(\"c\") })";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let keys: Vec<&str> = fields.iter().map(|field| field.key.node.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+
+        let b = &fields[1].value;
+        assert_eq!(&schema[b.span.start..b.span.end], "z.array(z.boolean())");
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let data = vec![
+            (
+                "z.object({ id: z.strin() })",
+                Error::Syntax(SyntaxError::InvalidIdentifier(
+                    "strin".to_string(),
+                    Span::new(17, 22, 1, 18),
+                )),
+            ),
+            (
+                "z.object({ id: z.number() ",
+                Error::Syntax(SyntaxError::UnexpectedEndOfFile(Span::new(26, 26, 1, 27))),
+            ),
+            (
+                "z.array(z.number()]",
+                Error::Syntax(SyntaxError::UnexpectedToken(
+                    Token::RRound,
+                    Token::RSquare,
+                    Span::new(18, 19, 1, 19),
+                )),
+            ),
+            (
+                "z.string(), z.number()",
+                Error::Syntax(SyntaxError::UnexpectedToken(
+                    Token::Eof,
+                    Token::Comma,
+                    Span::new(10, 11, 1, 11),
+                )),
+            ),
+            (
+                "z.string().regex(/[z-a]/)",
+                Error::Syntax(SyntaxError::InvalidRegex(
+                    RegexError::InvalidRange,
+                    Span::new(17, 24, 1, 18),
+                )),
+            ),
+            (
+                r"z.string().regex(/^(?=\d)[a-z]$/)",
+                Error::Syntax(SyntaxError::InvalidRegex(
+                    RegexError::Unsupported,
+                    Span::new(17, 32, 1, 18),
+                )),
+            ),
+            (
+                "z.literal([1])",
+                Error::Syntax(SyntaxError::UnexpectedTokenInValue(
+                    Token::LSquare,
+                    Span::new(10, 11, 1, 11),
+                )),
+            ),
+            (
+                r"z.string().regex(/^\d{2}$/).min(3)",
+                Error::Syntax(SyntaxError::UnsatisfiableString(
+                    "min".to_string(),
+                    Span::new(28, 34, 1, 29),
+                )),
+            ),
+            (
+                r#"z.string().regex(/^\d+$/).startsWith("ab")"#,
+                Error::Syntax(SyntaxError::UnsatisfiableString(
+                    "startsWith".to_string(),
+                    Span::new(26, 42, 1, 27),
+                )),
+            ),
+            (
+                r"z.string().regex(/^[^\w!-@]$/).min(2)",
+                Error::Syntax(SyntaxError::UnsatisfiableString(
+                    "min".to_string(),
+                    Span::new(31, 37, 1, 32),
+                )),
+            ),
+            (
+                "z.string().email().max(5)",
+                Error::Syntax(SyntaxError::UnsatisfiableString(
+                    "max".to_string(),
+                    Span::new(19, 25, 1, 20),
+                )),
+            ),
+            (
+                r#"z.string().uuid().startsWith("x")"#,
+                Error::Syntax(SyntaxError::UnsatisfiableString(
+                    "startsWith".to_string(),
+                    Span::new(18, 33, 1, 19),
+                )),
+            ),
+            (
+                "z.string().nanoid().min(22)",
+                Error::Syntax(SyntaxError::UnsatisfiableString(
+                    "min".to_string(),
+                    Span::new(20, 27, 1, 21),
+                )),
+            ),
+            (
+                "z.string().min(1.5)",
+                Error::Syntax(SyntaxError::UnexpectedTokenInValue(
+                    Token::Number(Number::Float(1.5)),
+                    Span::new(15, 18, 1, 16),
+                )),
+            ),
+            (
+                r#"z.date().min(new Date("2023-02-29"))"#,
+                Error::Syntax(SyntaxError::InvalidDate(
+                    "2023-02-29".to_string(),
+                    Span::new(22, 34, 1, 23),
+                )),
+            ),
+            (
+                "z.object({ id: z.number(); })",
+                Error::Syntax(SyntaxError::UnexpectedToken(
+                    Token::Comma,
+                    Token::Semicolon,
+                    Span::new(25, 26, 1, 26),
+                )),
+            ),
+            (
+                "z.object({ id: z.number() @ })",
+                Error::Syntax(SyntaxError::UnexpectedToken(
+                    Token::Comma,
+                    Token::Punct('@'),
+                    Span::new(26, 27, 1, 27),
+                )),
+            ),
+            (
+                "z.object({ id: z.number() § })",
+                Error::Lexer(LexerError::IllegalCharacter('§', Span::new(26, 28, 1, 27))),
+            ),
+            (
+                "z.number() > 1",
+                Error::Syntax(SyntaxError::UnexpectedToken(
+                    Token::Eof,
+                    Token::Punct('>'),
+                    Span::new(11, 12, 1, 12),
+                )),
+            ),
+            (
+                "z.number();;",
+                Error::Syntax(SyntaxError::UnexpectedToken(
+                    Token::Eof,
+                    Token::Semicolon,
+                    Span::new(11, 12, 1, 12),
+                )),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_recovering() {
+        let schema = "z.object({
+  a: z.strin(),
+  b: z.number(),
+  c: z.array(z.foo()),
+  d: z.enum([\"x\", 1]),
+  e: z.boolean();
+})";
+        let (tree, errors) = get_syntax_tree_recovering(schema);
+
+        let spans: Vec<(usize, usize)> = errors
+            .iter()
+            .map(|err| (err.span().line, err.span().column))
+            .collect();
+        assert_eq!(spans, vec![(2, 8), (4, 16), (5, 19), (6, 17)]);
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let keys: Vec<&str> = fields.iter().map(|field| field.key.node.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b", "c", "d", "e"]);
+
+        assert!(matches!(fields[0].value.node, ZodExpression::Error));
+        assert!(matches!(fields[1].value.node, ZodExpression::Number(_)));
+        assert!(
+            matches!(&fields[2].value.node, ZodExpression::Array(item) if matches!(item.node, ZodExpression::Error))
+        );
+        assert!(matches!(&fields[3].value.node, ZodExpression::Enum(values) if values == &["x"]));
+        assert!(matches!(fields[4].value.node, ZodExpression::Boolean));
+    }
+
+    #[test]
+    fn test_object_separators() {
+        let data = vec![
+            (
+                "z.object({ a: z.string() b: z.number() })",
+                SyntaxError::UnexpectedToken(
+                    Token::Comma,
+                    Token::Ident("b".into()),
+                    Span::new(25, 26, 1, 26),
+                ),
+                vec!["a", "b"],
+            ),
+            (
+                "z.object({ a: z.string() ), b: z.number() })",
+                SyntaxError::UnexpectedToken(Token::Comma, Token::RRound, Span::new(25, 26, 1, 26)),
+                vec!["a", "b"],
+            ),
+            (
+                "z.object({ a: z.string(), , b: z.number() })",
+                SyntaxError::UnexpectedTokenInObjectBody(Token::Comma, Span::new(26, 27, 1, 27)),
+                vec!["a"],
+            ),
+        ];
+
+        for (schema, expected, keys) in data {
+            let expected = Error::Syntax(expected);
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+
+            let (tree, errors) = get_syntax_tree_recovering(schema);
+            assert_eq!(errors, vec![expected], "schema={schema:?}");
+            let ZodExpression::Object(fields, _) = &tree.node else {
+                panic!("expected object, got={:?}", tree.node);
+            };
+            let found: Vec<&str> = fields.iter().map(|field| field.key.node.as_str()).collect();
+            assert_eq!(found, keys, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_recovering_arguments() {
+        let data = vec![
+            ("z.string().regex(/[z-a]/)", 1),
+            (r#"z.tuple([z.string().min("x"), z.number()])"#, 1),
+            ("z.object({a: z.string()}).pick({a: false})", 1),
+            (
+                r#"z.object({ a: z.number().min("x", { message: "m" }).int(), b: z.boolean() })"#,
+                1,
+            ),
+            (
+                r#"z.object({ a: z.string().min("x"), b: z.strin(), c: z.number().max([1]) })"#,
+                3,
+            ),
+            ("z.object({ a: z.array(z.strin()), b: z.number() })", 1),
+        ];
+
+        for (schema, expected) in data {
+            let (_, errors) = get_syntax_tree_recovering(schema);
+            assert_eq!(
+                errors.len(),
+                expected,
+                "schema={schema:?} errors={errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unbalanced_arguments() {
+        let data = vec![
+            (
+                r#"z.string().describe("x"]"#,
+                SyntaxError::UnexpectedToken(
+                    Token::RRound,
+                    Token::RSquare,
+                    Span::new(23, 24, 1, 24),
+                ),
+            ),
+            (
+                "z.object({a: z.number().min(1}, b: z.string()})",
+                SyntaxError::UnexpectedToken(
+                    Token::RRound,
+                    Token::RCurly,
+                    Span::new(29, 30, 1, 30),
+                ),
+            ),
+            (
+                r#"z.object({a: z.string().describe("x"}, b: z.string()})"#,
+                SyntaxError::UnexpectedToken(
+                    Token::RRound,
+                    Token::RCurly,
+                    Span::new(36, 37, 1, 37),
+                ),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let expected = Error::Syntax(expected);
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+
+            let (_, errors) = get_syntax_tree_recovering(schema);
+            assert_eq!(errors.first(), Some(&expected), "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let schema = "// order payload
+z.object({
+  /** Internal order id */
+  id: z.number(), // trailing comment
+  /*
+   * Not a doc comment
+   */
+  status: z.string(),
+  /**
+   * Customer email.
+   * Used for receipts.
+   */
+  email: z.string().email(),
+})";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let docs: Vec<(&str, Option<&str>)> = fields
+            .iter()
+            .map(|field| (field.key.node.as_str(), field.doc.as_deref()))
+            .collect();
+        assert_eq!(
+            docs,
+            vec![
+                ("id", Some("Internal order id")),
+                ("status", None),
+                ("email", Some("Customer email.\nUsed for receipts.")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let schema = "z.object({
+  comment: z.string().min(1).optional(),
+  email: z.string().email().nullish(),
+  parent: z.number().nullable().optional()
+})";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+
+        let comment = &fields[0].value;
+        assert_eq!(
+            &schema[comment.span.start..comment.span.end],
+            "z.string().min(1).optional()"
+        );
+        let ZodExpression::Optional(inner) = &comment.node else {
+            panic!("expected optional, got={:?}", comment.node);
+        };
+        assert!(matches!(inner.node, ZodExpression::String(_)));
+        assert_eq!(
+            &schema[inner.span.start..inner.span.end],
+            "z.string().min(1)"
+        );
+
+        assert!(
+            matches!(&fields[1].value.node, ZodExpression::Nullish(inner) if matches!(&inner.node, ZodExpression::String(checks) if checks.format == Some(StringFormat::Email)))
+        );
+        assert!(matches!(
+            &fields[2].value.node,
+            ZodExpression::Optional(inner) if matches!(&inner.node, ZodExpression::Nullable(inner) if matches!(inner.node, ZodExpression::Number(_)))
+        ));
+    }
+
+    #[test]
+    fn test_default_values() {
+        let schema = "z.string().nullable().default({ a: [1, 'x', false], b: null })";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Default(inner, value) = &tree.node else {
+            panic!("expected default, got={:?}", tree.node);
+        };
+        assert!(matches!(inner.node, ZodExpression::Nullable(_)));
+        assert_eq!(
+            value,
+            &Value::Object(vec![
+                (
+                    "a".to_string(),
+                    Value::Array(vec![
+                        Value::Number(Number::Integer(1)),
+                        Value::String("x".to_string()),
+                        Value::Boolean(false),
+                    ])
+                ),
+                ("b".to_string(), Value::Null),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_string_checks() {
+        let schema = r#"z.string().trim().min(3, "too short").max(10).regex(/^[a-z]+\/\d$/i).startsWith("a").includes("b", { position: 1 }).toUpperCase()"#;
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::String(checks) = &tree.node else {
+            panic!("expected string, got={:?}", tree.node);
+        };
+        assert_eq!(
+            checks,
+            &StringChecks {
+                min: Some(3),
+                max: Some(10),
+                regex: Some(Regex {
+                    pattern: r"^[a-z]+\/\d$".to_string(),
+                    flags: "i".to_string(),
+                }),
+                format: None,
+                starts_with: Some("a".to_string()),
+                ends_with: None,
+                includes: vec!["b".to_string()],
+                transforms: vec![StringTransform::Trim, StringTransform::ToUpperCase],
+            }
+        );
+    }
+
+    #[test]
+    fn test_string_formats() {
+        let data = vec![
+            ("z.string().email()", StringFormat::Email),
+            ("z.string().url({ message: 'bad url' })", StringFormat::Url),
+            ("z.string().cuid2('bad id')", StringFormat::Cuid2),
+            (
+                "z.string().datetime({ offset: true, precision: 3 })",
+                StringFormat::Datetime(DatetimeOptions {
+                    offset: true,
+                    local: false,
+                    precision: Some(3),
+                }),
+            ),
+            (
+                "z.string().datetime()",
+                StringFormat::Datetime(DatetimeOptions::default()),
+            ),
+            (
+                "z.string().time({ precision: 0 })",
+                StringFormat::Time(Some(0)),
+            ),
+            (
+                r#"z.string().ip({ version: "v6" })"#,
+                StringFormat::Ip(Some(IpVersion::V6)),
+            ),
+            ("z.string().cidr()", StringFormat::Cidr(None)),
+            ("z.string().uuid().ulid()", StringFormat::Ulid),
+        ];
+
+        for (schema, expected) in data {
+            let tree = get_syntax_tree(schema).unwrap();
+            let ZodExpression::String(checks) = &tree.node else {
+                panic!("expected string, got={:?}", tree.node);
+            };
+            assert_eq!(checks.format, Some(expected), "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_tuple() {
+        let schema = "z.tuple([z.string(), z.number(),]).rest(z.boolean())";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Tuple { items, rest } = &tree.node else {
+            panic!("expected tuple, got={:?}", tree.node);
+        };
+        assert!(matches!(items[0].node, ZodExpression::String(_)));
+        assert!(matches!(items[1].node, ZodExpression::Number(_)));
+        assert_eq!(items.len(), 2);
+
+        let rest = rest.as_ref().unwrap();
+        assert!(matches!(rest.node, ZodExpression::Boolean));
+        assert_eq!(&schema[rest.span.start..rest.span.end], "z.boolean()");
+    }
+
+    #[test]
+    fn test_collections() {
+        let schema = "z.object({
+  a: z.record(z.number()),
+  b: z.record(z.enum(['x', 'y']), z.string()),
+  c: z.map(z.string(), z.number()),
+  d: z.set(z.string()).min(1),
+})";
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        assert!(matches!(
+            &fields[0].value.node,
+            ZodExpression::Record { key: None, value } if matches!(value.node, ZodExpression::Number(_))
+        ));
+        assert!(matches!(
+            &fields[1].value.node,
+            ZodExpression::Record { key: Some(key), .. } if matches!(key.node, ZodExpression::Enum(_))
+        ));
+        assert!(matches!(
+            &fields[2].value.node,
+            ZodExpression::Map { key, value } if matches!(key.node, ZodExpression::String(_)) && matches!(value.node, ZodExpression::Number(_))
+        ));
+        assert!(matches!(&fields[3].value.node, ZodExpression::Set(_)));
+    }
+
+    #[test]
+    fn test_dates() {
+        let tree =
+            get_syntax_tree("z.date().min(new Date(0)).max(new Date('1970-01-02'))").unwrap();
+        assert!(matches!(
+            tree.node,
+            ZodExpression::Date(DateChecks {
+                min: Some(0),
+                max: Some(86_400_000)
+            })
+        ));
+
+        let data = vec![
+            ("1970-01-01", Some("1970-01-01T00:00:00.000Z")),
+            ("2000-02-29T23:59:59Z", Some("2000-02-29T23:59:59.000Z")),
+            ("1969-12-31T23:00-01:30", Some("1970-01-01T00:30:00.000Z")),
+            ("2024-06-01T08:15:30.1234", Some("2024-06-01T08:15:30.123Z")),
+            ("0001-01-01", Some("0001-01-01T00:00:00.000Z")),
+            ("1900-02-29", None),
+            ("2024-13-01", None),
+            ("2024-01-01T24:00", None),
+            ("24-01-01", None),
+            ("2024-01-01T10:00.", None),
+        ];
+
+        for (date, expected) in data {
+            let formatted = parse_date(date).map(format_date);
+            assert_eq!(formatted.as_deref(), expected, "date={date:?}");
+        }
+    }
+
+    #[test]
+    fn test_literals() {
+        let data = vec![
+            ("z.literal('a')", Value::String("a".to_string())),
+            ("z.literal(42)", Value::Number(Number::Integer(42))),
+            ("z.literal(0.5)", Value::Number(Number::Float(0.5))),
+            ("z.literal(-7n)", Value::Number(Number::BigInt(-7))),
+            ("z.literal(false)", Value::Boolean(false)),
+            ("z.literal(null)", Value::Null),
+        ];
+
+        for (schema, expected) in data {
+            let tree = get_syntax_tree(schema).unwrap();
+            assert!(
+                matches!(&tree.node, ZodExpression::Literal(value) if value == &expected),
+                "schema={schema:?}, got={:?}",
+                tree.node
+            );
+        }
+    }
+
+    #[test]
+    fn test_native_enums() {
+        let schema = r#"export enum OrderStatus {
+  Created = "CREATED",
+  Paid = 'PAID',
+}
+const enum Priority { Low, Medium = 5, High, "very high" }
+
+z.object({ status: z.nativeEnum(OrderStatus), priority: z.nativeEnum(Priority) })"#;
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let ZodExpression::NativeEnum(status) = &fields[0].value.node else {
+            panic!("expected native enum, got={:?}", fields[0].value.node);
+        };
+        assert_eq!(status.name, "OrderStatus");
+        assert_eq!(
+            status.members,
+            vec![
+                ("Created".to_string(), Value::String("CREATED".to_string())),
+                ("Paid".to_string(), Value::String("PAID".to_string())),
+            ]
+        );
+
+        let ZodExpression::NativeEnum(priority) = &fields[1].value.node else {
+            panic!("expected native enum, got={:?}", fields[1].value.node);
+        };
+        let values: Vec<&Value> = priority.members.iter().map(|(_, value)| value).collect();
+        assert_eq!(
+            values,
+            vec![
+                &Value::Number(Number::Integer(0)),
+                &Value::Number(Number::Integer(5)),
+                &Value::Number(Number::Integer(6)),
+                &Value::Number(Number::Integer(7)),
+            ]
+        );
+
+        let data = vec![
+            (
+                "z.nativeEnum(Missing)",
+                Error::Syntax(SyntaxError::UndeclaredEnum(
+                    "Missing".to_string(),
+                    Span::new(13, 20, 1, 14),
+                )),
+            ),
+            (
+                "enum E { A = 'a', B } z.nativeEnum(E)",
+                Error::Syntax(SyntaxError::MissingEnumInitializer(
+                    "B".to_string(),
+                    Span::new(18, 19, 1, 19),
+                )),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_discriminated_union() {
+        let schema = r#"z.discriminatedUnion("type", [
+  z.object({ type: z.literal("created"), id: z.number() }),
+  z.object({ type: z.literal("paid"), amount: z.number() }),
+])"#;
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::DiscriminatedUnion {
+            discriminator,
+            options,
+        } = &tree.node
+        else {
+            panic!("expected discriminated union, got={:?}", tree.node);
+        };
+        assert_eq!(discriminator, "type");
+        assert_eq!(options.len(), 2);
+
+        let data = vec![
+            (
+                r#"z.discriminatedUnion("type", [z.string()])"#,
+                Error::Syntax(SyntaxError::InvalidUnionOption(
+                    "type".to_string(),
+                    Span::new(30, 40, 1, 31),
+                )),
+            ),
+            (
+                r#"z.discriminatedUnion("type", [z.object({ type: z.string() })])"#,
+                Error::Syntax(SyntaxError::InvalidUnionOption(
+                    "type".to_string(),
+                    Span::new(30, 60, 1, 31),
+                )),
+            ),
+            (
+                r#"z.discriminatedUnion("kind", [z.object({ type: z.literal("a") })])"#,
+                Error::Syntax(SyntaxError::InvalidUnionOption(
+                    "kind".to_string(),
+                    Span::new(30, 64, 1, 31),
+                )),
+            ),
+            (
+                r#"z.discriminatedUnion("type", [z.object({ type: z.literal(1) }), z.object({ type: z.literal(1) })])"#,
+                Error::Syntax(SyntaxError::DuplicateDiscriminator(
+                    "1".to_string(),
+                    Span::new(81, 93, 1, 82),
+                )),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_intersection() {
+        let tree = get_syntax_tree("z.intersection(z.object({ a: z.string() }), z.any())").unwrap();
+        assert!(
+            matches!(tree.node, ZodExpression::Intersection(_, _)),
+            "got={:?}",
+            tree.node
+        );
+
+        let tree =
+            get_syntax_tree("z.object({ a: z.string() }).and(z.object({ a: z.literal('x') }))")
+                .unwrap();
+        let ZodExpression::Intersection(a, b) = &tree.node else {
+            panic!("expected intersection, got={:?}", tree.node);
+        };
+        assert_eq!(a.span, Span::new(0, 27, 1, 1));
+        assert_eq!(b.span, Span::new(32, 63, 1, 33));
+
+        let data = vec![
+            (
+                "z.intersection(z.object({ a: z.string() }), z.object({ a: z.number() }))",
+                Error::Syntax(SyntaxError::IntersectionConflict(
+                    "a".to_string(),
+                    Span::new(55, 56, 1, 56),
+                )),
+            ),
+            (
+                "z.object({ a: z.literal(1) }).and(z.object({ b: z.string() })).and(z.object({ a: z.literal(2) }))",
+                Error::Syntax(SyntaxError::IntersectionConflict(
+                    "a".to_string(),
+                    Span::new(78, 79, 1, 79),
+                )),
+            ),
+            (
+                "z.object({ o: z.object({ id: z.boolean() }) }).and(z.object({ o: z.object({ id: z.date() }).optional() }))",
+                Error::Syntax(SyntaxError::IntersectionConflict(
+                    "id".to_string(),
+                    Span::new(76, 78, 1, 77),
+                )),
+            ),
+            (
+                "z.object({ a: z.number() }).and(z.object({ a: z.literal(10n) }))",
+                Error::Syntax(SyntaxError::IntersectionConflict(
+                    "a".to_string(),
+                    Span::new(43, 44, 1, 44),
+                )),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_object_methods() {
+        let tree = get_syntax_tree(
+            "z.object({ a: z.string() }).extend({ b: z.number() }).omit({ a: true })",
+        )
+        .unwrap();
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let keys: Vec<&str> = fields.iter().map(|field| field.key.node.as_str()).collect();
+        assert_eq!(keys, vec!["b"]);
+
+        let data = vec![
+            (
+                "z.object({}).pick({ a: false })",
+                Error::Syntax(SyntaxError::InvalidObjectMethodArgument(
+                    "pick".to_string(),
+                    Span::new(18, 30, 1, 19),
+                )),
+            ),
+            (
+                "z.object({}).partial({ a: 1 })",
+                Error::Syntax(SyntaxError::InvalidObjectMethodArgument(
+                    "partial".to_string(),
+                    Span::new(21, 29, 1, 22),
+                )),
+            ),
+            (
+                "z.object({}).merge(z.string())",
+                Error::Syntax(SyntaxError::InvalidObjectMethodArgument(
+                    "merge".to_string(),
+                    Span::new(19, 29, 1, 20),
+                )),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let data = vec![
+            ("z.object({})", "Strip"),
+            ("z.object({}).strict()", "Strict"),
+            ("z.object({}).passthrough()", "Passthrough"),
+            ("z.object({}).strict().strip()", "Strip"),
+            ("z.object({}).catchall(z.number())", "Catchall"),
+            ("z.object({}).strict().extend({ a: z.string() })", "Strict"),
+            (
+                "z.object({}).strict().merge(z.object({}).passthrough())",
+                "Passthrough",
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let tree = get_syntax_tree(schema).unwrap();
+            let ZodExpression::Object(_, unknown_keys) = &tree.node else {
+                panic!("expected object, got={:?}", tree.node);
+            };
+            let policy = match unknown_keys {
+                UnknownKeys::Strip => "Strip",
+                UnknownKeys::Strict => "Strict",
+                UnknownKeys::Passthrough => "Passthrough",
+                UnknownKeys::Catchall(_) => "Catchall",
+            };
+            assert_eq!(policy, expected, "schema={schema:?}");
+        }
+    }
+
+    #[test]
+    fn test_object_keys() {
+        let schema = r#"export const CONTENT_TYPE = "content-type";
+const VERSION = 2 as const;
+
+z.object({
+  id: z.string(),
+  "x-id": z.string(),
+  'x "quoted"': z.string(),
+  1: z.number(),
+  1.5: z.number(),
+  [CONTENT_TYPE]: z.string(),
+  [VERSION]: z.number(),
+  ["computed"]: z.boolean(),
+})"#;
+        let tree = get_syntax_tree(schema).unwrap();
+
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let keys: Vec<&str> = fields.iter().map(|field| field.key.node.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "id",
+                "x-id",
+                "x \"quoted\"",
+                "1",
+                "1.5",
+                "content-type",
+                "2",
+                "computed"
+            ]
+        );
+        assert_eq!(fields[5].key.span, Span::new(190, 204, 10, 3));
+
+        let data = vec![
+            (
+                "z.object({ [KEY]: z.string() })",
+                Error::Syntax(SyntaxError::UndeclaredConstant(
+                    "KEY".to_string(),
+                    Span::new(12, 15, 1, 13),
+                )),
+            ),
+            (
+                "const KEY = z.string(); z.object({})",
+                Error::Syntax(SyntaxError::UnexpectedTokenInValue(
+                    Token::Ident("z".into()),
+                    Span::new(12, 13, 1, 13),
+                )),
+            ),
+            (
+                "z.object({ [1]: z.string() })",
+                Error::Syntax(SyntaxError::UnexpectedTokenInObjectBody(
+                    Token::Number(Number::Integer(1)),
+                    Span::new(12, 13, 1, 13),
+                )),
+            ),
+        ];
+
+        for (schema, expected) in data {
+            let err = get_syntax_tree(schema).unwrap_err();
+            assert_eq!(err, expected, "schema={schema:?}");
+        }
+
+        let (tree, errors) =
+            get_syntax_tree_recovering("z.object({ [KEY]: z.string(), b: z.number() })");
+        assert_eq!(
+            errors,
+            vec![Error::Syntax(SyntaxError::UndeclaredConstant(
+                "KEY".to_string(),
+                Span::new(12, 15, 1, 13),
+            ))]
+        );
+        let ZodExpression::Object(fields, _) = &tree.node else {
+            panic!("expected object, got={:?}", tree.node);
+        };
+        let keys: Vec<&str> = fields.iter().map(|field| field.key.node.as_str()).collect();
+        assert_eq!(keys, vec!["b"]);
+    }
+}